@@ -6,10 +6,16 @@ from pathlib import Path
 plots_folder = Path(__file__).parent / 'plots'
 plots_folder.mkdir(exist_ok=True)
 
-# Load the data with columns: n list_type distribution  total_cost
-data = pd.read_csv('l1.csv', delimiter=';')
-data['avg_cost'] = data['total_cost'] / data['n']
-print(data.head())
+# Load the data with columns:
+# workload n values list_type distribution seed total_cost opt_cost opt_exact competitive_ratio
+results = pd.read_csv('l1.csv', delimiter=';')
+results['avg_cost'] = results['total_cost'] / results['n']
+print(results.head())
+# OPT is only known for the static workload
+dynamic = results[results['workload'] == 'Dynamic']
+results = results[results['workload'] == 'Static']
+# The cost plots use the large alphabet, whose OPT is only bounded from below
+data = results[results['values'] == results['values'].max()]
 
 # Ensure consistent colors for each list_type across plots
 hue_order = data['list_type'].unique()
@@ -28,3 +34,25 @@ subset = subset[subset['n'] >= 5000]  # Only show n >= 1000
 sns.lineplot(data=subset, x='n', y='avg_cost', hue='list_type', hue_order=hue_order, palette=palette)
 plt.title('Distribution: Uniform')
 plt.savefig(plots_folder / 'distribution_Uniform_zoomed.png', dpi=300)
+plt.close()
+
+# Competitive ratio against the exact offline optimum (small alphabet), and the ratio against
+# its lower bound (large alphabet), which overstates the competitive ratio
+for exact, name, label in [(True, 'competitive_ratio', 'Competitive ratio'),
+                           (False, 'ratio_vs_lower_bound', 'Ratio to the OPT lower bound')]:
+    ratios = results[results['opt_exact'] == exact]
+    for distribution in ratios['distribution'].unique():
+        subset = ratios[ratios['distribution'] == distribution]
+        sns.lineplot(data=subset, x='n', y='competitive_ratio', hue='list_type', hue_order=hue_order, palette=palette)
+        plt.title(f'{label}, distribution: {distribution}')
+        plt.savefig(plots_folder / f'{name}_{distribution}.png', dpi=300)
+        plt.close()
+
+# Dynamic dictionary workload: accesses, insertions and deletions
+dynamic = dynamic[dynamic['values'] == dynamic['values'].max()]
+for distribution in dynamic['distribution'].unique():
+    subset = dynamic[dynamic['distribution'] == distribution]
+    sns.lineplot(data=subset, x='n', y='avg_cost', hue='list_type', hue_order=hue_order, palette=palette)
+    plt.title(f'Dynamic dictionary, distribution: {distribution}')
+    plt.savefig(plots_folder / f'dynamic_{distribution}.png', dpi=300)
+    plt.close()
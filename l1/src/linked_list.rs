@@ -1,6 +1,11 @@
+use online_core::OnlineAlgorithm;
+use online_core::seed::splitmix64;
+use rand::Rng;
+use rand::rngs::StdRng;
 use std::collections::HashMap;
 
 /// Enum representing the type of linked list.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum ListType {
     /// A simple linked list with no special behavior.
@@ -11,24 +16,154 @@ pub enum ListType {
     Transpose,
     /// A linked list that maintains elements sorted by access count.
     Count(HashMap<u32, u32>),
+    /// Randomized BIT: each element has a random bit, complemented on access;
+    /// the element moves to the front whenever its bit becomes 1.
+    Bit(HashMap<u32, bool>, StdRng),
+    /// Deterministic counterpart of BIT: every element moves to the front on every other access.
+    MoveEveryOther(HashMap<u32, bool>),
+    /// TIMESTAMP (Albers): the accessed element is inserted in front of the first preceding
+    /// element that was requested at most once since the last request to the accessed one.
+    /// Stores the times of the last two requests to each element.
+    Timestamp(HashMap<u32, (usize, Option<usize>)>),
+    /// A linked list sorted by access counts that decay by the given factor on every access.
+    /// The factor lies in (0, 1], 1 giving plain access counts. Stores the logarithm of each
+    /// element's (undecayed) score.
+    CountDecay(HashMap<u32, f64>, f64),
 }
 
-/// A node in the linked list.
+impl ListType {
+    /// Returns the name of the list type, as used in the results.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ListType::Simple => "Simple",
+            ListType::MoveToFront => "MoveToFront",
+            ListType::Transpose => "Transpose",
+            ListType::Count(_) => "Count",
+            ListType::Bit(_, _) => "Bit",
+            ListType::MoveEveryOther(_) => "MoveEveryOther",
+            ListType::Timestamp(_) => "Timestamp",
+            ListType::CountDecay(_, _) => "CountDecay",
+        }
+    }
+}
+
+/// A request of the dynamic list update problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Request {
+    /// Access a value, appending it to the back of the list if it is missing.
+    Access(u32),
+    /// Insert a value at the back of the list; a present value is only searched for.
+    Insert(u32),
+    /// Delete a value from the list; a missing value is only searched for.
+    Delete(u32),
+}
+
+/// The cost of a single access, split into its components.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessCost {
+    /// The number of nodes searched to find the value.
+    pub search: u32,
+    /// Exchanges that move the accessed element toward the front.
+    pub free_exchanges: u32,
+    /// Exchanges of neighbouring elements other than the accessed one.
+    pub paid_exchanges: u32,
+}
+
+impl AccessCost {
+    /// Creates the cost of an access that did not reorganise the list.
+    fn searched(search: u32) -> Self {
+        AccessCost {
+            search,
+            ..Default::default()
+        }
+    }
+
+    /// Creates the cost of an access that moved the accessed element from index `from` to
+    /// index `to`. Moving it toward the front takes free exchanges; moving it toward the back
+    /// exchanges it with elements that move forward, which are paid.
+    fn moved(search: u32, from: u32, to: u32) -> Self {
+        AccessCost {
+            search,
+            free_exchanges: from.saturating_sub(to),
+            paid_exchanges: to.saturating_sub(from),
+        }
+    }
+
+    /// Returns the total cost of the access under the given cost model.
+    pub fn total(&self, model: CostModel) -> u32 {
+        self.search
+            + self.free_exchanges * model.free_exchange
+            + self.paid_exchanges * model.paid_exchange
+    }
+}
+
+/// Prices of the exchanges performed while reorganising the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostModel {
+    /// The cost of one free exchange.
+    pub free_exchange: u32,
+    /// The cost of one paid exchange.
+    pub paid_exchange: u32,
+}
+
+impl CostModel {
+    /// The standard Sleator–Tarjan model: moving the accessed element toward the front is
+    /// free, every other exchange costs 1.
+    pub const SLEATOR_TARJAN: CostModel = CostModel {
+        free_exchange: 0,
+        paid_exchange: 1,
+    };
+    /// Only searching is charged; the list may be reorganised for free.
+    pub const SEARCH_ONLY: CostModel = CostModel {
+        free_exchange: 0,
+        paid_exchange: 0,
+    };
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        CostModel::SLEATOR_TARJAN
+    }
+}
+
+/// A node in the linked list, stored in the list's arena.
 #[derive(Debug, Clone)]
 struct Node {
     /// The value stored in the node.
     value: u32,
-    /// The next node in the list.
-    next: Option<Box<Node>>,
+    /// The slot of the previous node in the list.
+    prev: Option<usize>,
+    /// The slot of the next node in the list.
+    next: Option<usize>,
 }
 
 /// A linked list with various access strategies.
+///
+/// Nodes live in a vector and link to each other by index, so moving a node is an O(1)
+/// splice. A map from values to slots makes finding a node O(1); the search cost charged
+/// for an access is still the position of the value in the list.
 #[derive(Debug)]
 pub struct LinkedList {
-    /// The head of the linked list.
-    head: Option<Box<Node>>,
+    /// The arena holding the nodes.
+    nodes: Vec<Node>,
+    /// Slots of `nodes` freed by removals, reused for new nodes.
+    free_slots: Vec<usize>,
+    /// The slot of every value in the list.
+    slots: HashMap<u32, usize>,
+    /// The slot of the head of the linked list.
+    head: Option<usize>,
+    /// The slot of the tail of the linked list.
+    tail: Option<usize>,
+    /// The number of nodes in the list.
+    len: usize,
+    /// For a Count list, the slot of the first node with each access count.
+    count_heads: HashMap<u32, usize>,
     /// The type of the linked list.
     list_type: ListType,
+    /// The number of accesses served so far.
+    clock: usize,
+    /// The cost model charging the requests served as an `OnlineAlgorithm`.
+    cost_model: CostModel,
 }
 
 impl LinkedList {
@@ -41,13 +176,39 @@ impl LinkedList {
     /// # Returns
     ///
     /// A new `LinkedList` instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list type is CountDecay with a decay factor outside (0, 1], whose
+    /// scores would be meaningless.
     pub fn new(list_type: ListType) -> Self {
+        if let ListType::CountDecay(_, decay) = list_type {
+            assert!(0.0 < decay && decay <= 1.0, "Decay factor must lie in (0, 1]");
+        }
         LinkedList {
+            nodes: Vec::new(),
+            free_slots: Vec::new(),
+            slots: HashMap::new(),
             head: None,
+            tail: None,
+            len: 0,
+            count_heads: HashMap::new(),
             list_type,
+            clock: 0,
+            cost_model: CostModel::default(),
         }
     }
 
+    /// Sets the cost model charging the requests served as an `OnlineAlgorithm`. The list
+    /// starts with the Sleator–Tarjan model.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost_model` - The cost model to charge requests by.
+    pub fn set_cost_model(&mut self, cost_model: CostModel) {
+        self.cost_model = cost_model;
+    }
+
     /// Removes and returns the first element from the list.
     ///
     /// # Returns
@@ -55,12 +216,80 @@ impl LinkedList {
     /// The value of the first element, or `None` if the list is empty.
     #[allow(dead_code)]
     pub fn pop(&mut self) -> Option<u32> {
-        if let Some(mut head) = self.head.take() {
-            self.head = head.next.take();
-            Some(head.value)
-        } else {
-            None
+        let head = self.head?;
+        Some(self._remove(head))
+    }
+
+    /// Serves a request of the dynamic list update problem.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to serve.
+    ///
+    /// # Returns
+    ///
+    /// The cost of serving the request.
+    pub fn serve(&mut self, request: Request) -> AccessCost {
+        match request {
+            Request::Access(value) => self.access(value),
+            Request::Insert(value) => self.insert(value),
+            Request::Delete(value) => self.delete(value),
+        }
+    }
+
+    /// Inserts a value at the back of the list. Every list type treats the new value as it
+    /// treats a value accessed for the first time. Inserting a value that is already in the
+    /// list only costs the search for it and does not count as an access.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to insert.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the insertion: the number of nodes searched before the value was appended.
+    pub fn insert(&mut self, value: u32) -> AccessCost {
+        match self.slots.get(&value) {
+            Some(&slot) => {
+                self.clock += 1;
+                AccessCost::searched(self._index_of(slot) as u32 + 1)
+            }
+            None => self.access(value),
+        }
+    }
+
+    /// Deletes a value from the list, dropping everything the list type recorded about it.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to delete.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the deletion: the number of nodes searched to find the value.
+    pub fn delete(&mut self, value: u32) -> AccessCost {
+        self.clock += 1;
+        let Some(&slot) = self.slots.get(&value) else {
+            return AccessCost::searched(self.len as u32);
+        };
+        let index = self._index_of(slot) as u32;
+        self._remove(slot);
+        match &mut self.list_type {
+            ListType::Simple | ListType::MoveToFront | ListType::Transpose => {}
+            ListType::Count(counts) => {
+                counts.remove(&value);
+            }
+            ListType::Bit(bits, _) | ListType::MoveEveryOther(bits) => {
+                bits.remove(&value);
+            }
+            ListType::Timestamp(times) => {
+                times.remove(&value);
+            }
+            ListType::CountDecay(scores, _) => {
+                scores.remove(&value);
+            }
         }
+        AccessCost::searched(index + 1)
     }
 
     /// Accesses a value in the list, applying the behavior of the list type.
@@ -71,50 +300,149 @@ impl LinkedList {
     ///
     /// # Returns
     ///
-    /// The number of nodes searched to find the value.
-    pub fn access(&mut self, value: u32) -> u32 {
-        match self.list_type {
+    /// The cost of the access: the number of nodes searched to find the value
+    /// and the exchanges performed to reorganise the list.
+    pub fn access(&mut self, value: u32) -> AccessCost {
+        let cost = match self.list_type {
             ListType::Simple => self._simple_access(value),
             ListType::MoveToFront => self._mtf_access(value),
             ListType::Transpose => self._transpose_access(value),
             ListType::Count(_) => self._count_access(value),
-        }
+            ListType::Bit(_, _) => self._bit_access(value),
+            ListType::MoveEveryOther(_) => self._move_every_other_access(value),
+            ListType::Timestamp(_) => self._timestamp_access(value),
+            ListType::CountDecay(_, _) => self._count_decay_access(value),
+        };
+        self.clock += 1;
+        cost
     }
 
-    /// Accesses a value in a simple list. If not found, adds it to the back.
+    /// Finds the position of a node in the list by walking back to the head.
     ///
     /// # Arguments
     ///
-    /// * `value` - The value to access.
+    /// * `slot` - The slot of the node.
     ///
     /// # Returns
     ///
-    /// The number of nodes searched to find the value.
-    fn _simple_access(&mut self, value: u32) -> u32 {
-        match self.head {
+    /// The 0-based index of the node.
+    fn _index_of(&self, slot: usize) -> usize {
+        let mut index = 0;
+        let mut current = slot;
+        while let Some(prev) = self.nodes[current].prev {
+            index += 1;
+            current = prev;
+        }
+        index
+    }
+
+    /// Adds a value to the back of the list.
+    ///
+    /// # Returns
+    ///
+    /// The slot of the new node and the number of nodes searched before the value was appended.
+    fn _push_back(&mut self, value: u32) -> (usize, u32) {
+        let node = Node {
+            value,
+            prev: self.tail,
+            next: None,
+        };
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.nodes[slot] = node;
+                slot
+            }
             None => {
-                let new_node = Box::new(Node { value, next: None });
-                self.head = Some(new_node);
-                return 0;
+                self.nodes.push(node);
+                self.nodes.len() - 1
             }
-            Some(ref mut head) => {
-                if head.value == value {
-                    return 1;
+        };
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(slot),
+            None => self.head = Some(slot),
+        }
+        self.tail = Some(slot);
+        self.slots.insert(value, slot);
+        let searched_nodes = self.len as u32;
+        self.len += 1;
+        (slot, searched_nodes)
+    }
+
+    /// Detaches a node from its neighbours, leaving it in the arena.
+    fn _unlink(&mut self, slot: usize) {
+        let Node { prev, next, .. } = self.nodes[slot];
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Moves a node directly in front of another node.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The slot of the node to move.
+    /// * `before` - The slot of the node it is placed in front of.
+    fn _move_before(&mut self, slot: usize, before: usize) {
+        if slot == before {
+            return;
+        }
+        self._unlink(slot);
+        let prev = self.nodes[before].prev;
+        self.nodes[slot].prev = prev;
+        self.nodes[slot].next = Some(before);
+        self.nodes[before].prev = Some(slot);
+        match prev {
+            Some(prev) => self.nodes[prev].next = Some(slot),
+            None => self.head = Some(slot),
+        }
+    }
+
+    /// Removes a node from the list and frees its slot.
+    ///
+    /// # Returns
+    ///
+    /// The value stored in the node.
+    fn _remove(&mut self, slot: usize) -> u32 {
+        let value = self.nodes[slot].value;
+        if let ListType::Count(counts) = &self.list_type {
+            let count = counts[&value];
+            if self.count_heads.get(&count) == Some(&slot) {
+                match self.nodes[slot].next {
+                    Some(next) if counts[&self.nodes[next].value] == count => {
+                        self.count_heads.insert(count, next);
+                    }
+                    _ => {
+                        self.count_heads.remove(&count);
+                    }
                 }
             }
         }
-        let mut current = &mut self.head;
-        let mut searched_nodes = 1;
-        while let Some(node) = current.as_ref().unwrap().next.as_ref() {
-            searched_nodes += 1;
-            if node.value == value {
-                return searched_nodes;
-            }
-            current = &mut current.as_mut().unwrap().next;
+        self._unlink(slot);
+        self.slots.remove(&value);
+        self.free_slots.push(slot);
+        self.len -= 1;
+        value
+    }
+
+    /// Accesses a value in a simple list. If not found, adds it to the back.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to access.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _simple_access(&mut self, value: u32) -> AccessCost {
+        match self.slots.get(&value) {
+            Some(&slot) => AccessCost::searched(self._index_of(slot) as u32 + 1),
+            None => AccessCost::searched(self._push_back(value).1),
         }
-        let new_node = Box::new(Node { value, next: None });
-        current.as_mut().unwrap().next = Some(new_node);
-        searched_nodes
     }
 
     /// Accesses a value in a Move-To-Front list. Moves the value to the front if found.
@@ -125,44 +453,17 @@ impl LinkedList {
     ///
     /// # Returns
     ///
-    /// The number of nodes searched to find the value.
-    fn _mtf_access(&mut self, value: u32) -> u32 {
-        match self.head {
-            None => {
-                // If the list is empty, add the value as the first node
-                let new_node = Box::new(Node { value, next: None });
-                self.head = Some(new_node);
-                return 0;
-            }
-            Some(ref mut head) => {
-                // If the head is the target value, no need to move it
-                if head.value == value {
-                    return 1;
-                }
-            }
-        }
-    
-        let mut current = &mut self.head;
-        let mut searched_nodes = 1;
-    
-        // Traverse the list to find the target node
-        while let Some(ref mut node) = current.as_mut().unwrap().next {
-            if node.value == value {
-                // Found the target node, move it to the front
-                let mut found_node = current.as_mut().unwrap().next.take(); // Remove the node
-                current.as_mut().unwrap().next = found_node.as_mut().unwrap().next.take(); // Update the `next` pointer of the previous node
-                found_node.as_mut().unwrap().next = self.head.take(); // Point the found node to the current head
-                self.head = found_node; // Update the head to the found node
-                return searched_nodes + 1;
+    /// The cost of the access.
+    fn _mtf_access(&mut self, value: u32) -> AccessCost {
+        match self.slots.get(&value) {
+            Some(&slot) => {
+                let index = self._index_of(slot) as u32;
+                self._move_before(slot, self.head.unwrap());
+                AccessCost::moved(index + 1, index, 0)
             }
-            searched_nodes += 1;
-            current = &mut current.as_mut().unwrap().next;
+            // If the value is not found, add it to the back
+            None => AccessCost::searched(self._push_back(value).1),
         }
-    
-        // If the value is not found, add it to the back
-        let new_node = Box::new(Node { value, next: None });
-        current.as_mut().unwrap().next = Some(new_node);
-        searched_nodes
     }
 
     /// Accesses a value in a Transpose list. Swaps the value with its predecessor if found.
@@ -173,128 +474,298 @@ impl LinkedList {
     ///
     /// # Returns
     ///
-    /// The number of nodes searched to find the value.
-    fn _transpose_access(&mut self, value: u32) -> u32 {
-        match self.head {
-            None => {
-                let new_node = Box::new(Node { value, next: None });
-                self.head = Some(new_node);
-                return 0;
-            }
-            Some(ref mut head) => {
-                if head.value == value {
-                    return 1;
+    /// The cost of the access.
+    fn _transpose_access(&mut self, value: u32) -> AccessCost {
+        match self.slots.get(&value) {
+            Some(&slot) => {
+                let index = self._index_of(slot) as u32;
+                if let Some(prev) = self.nodes[slot].prev {
+                    self._move_before(slot, prev);
                 }
+                AccessCost::moved(index + 1, index, index.saturating_sub(1))
             }
+            None => AccessCost::searched(self._push_back(value).1),
         }
-        let mut current = &mut self.head;
-        let mut searched_nodes = 1;
-        while let Some(node) = current.as_ref().unwrap().next.as_ref() {
-            if node.value == value {
-                let mut found_node = current.as_mut().unwrap().next.take();
-                let temp = current.take();
-                if let Some(mut temp_node) = temp {
-                    temp_node.next = found_node.as_mut().unwrap().next.take();
-                    found_node.as_mut().unwrap().next = Some(temp_node);
-                }
-                *current = Some(found_node.unwrap());
-                return searched_nodes + 1;
-            }
-            searched_nodes += 1;
-            current = &mut current.as_mut().unwrap().next;
-        }
-        let new_node = Box::new(Node { value, next: None });
-        current.as_mut().unwrap().next = Some(new_node);
-        searched_nodes
     }
 
     /// Accesses a value in a Count list. Increments the count and reorders the list.
     ///
+    /// The list is kept sorted by descending count, with ties in order of reaching the
+    /// count. After an increment the value moves in front of the first node that still
+    /// has its old count, which `count_heads` finds in O(1).
+    ///
     /// # Arguments
     ///
     /// * `value` - The value to access.
     ///
     /// # Returns
     ///
-    /// The number of nodes searched to find the value.
-    fn _count_access(&mut self, value: u32) -> u32 {
+    /// The cost of the access.
+    fn _count_access(&mut self, value: u32) -> AccessCost {
         let counts = match &mut self.list_type {
             ListType::Count(counts) => counts,
             _ => panic!("Invalid list type"),
         };
-        match self.head {
-            None => {
-                let new_node = Box::new(Node { value, next: None });
-                self.head = Some(new_node);
-                counts.insert(value, 1);
-                return 0;
+        let Some(&slot) = self.slots.get(&value) else {
+            counts.insert(value, 1);
+            let (slot, searched_nodes) = self._push_back(value);
+            self.count_heads.entry(1).or_insert(slot);
+            return AccessCost::searched(searched_nodes);
+        };
+
+        let count = counts[&value];
+        counts.insert(value, count + 1);
+        let first = self.count_heads[&count];
+        // Walk back to the head, noting how far the first node with the old count is.
+        let (mut index, mut distance) = (0, 0);
+        let mut current = slot;
+        while let Some(prev) = self.nodes[current].prev {
+            index += 1;
+            current = prev;
+            if current == first {
+                distance = index;
             }
-            Some(ref mut head) => {
-                if head.value == value {
-                    *counts.entry(value).or_insert(0) += 1;
-                    return 1;
+        }
+
+        if first == slot {
+            match self.nodes[slot].next {
+                Some(next) if counts[&self.nodes[next].value] == count => {
+                    self.count_heads.insert(count, next);
+                }
+                _ => {
+                    self.count_heads.remove(&count);
                 }
             }
+        } else {
+            self._move_before(slot, first);
         }
-        let mut current = &mut self.head;
-        let mut searched_nodes = 1;
-        while let Some(node) = current.as_ref().unwrap().next.as_ref() {
-            if node.value == value {
-                *counts.entry(value).or_insert(0) += 1;
-                self._reorder_by_count();
-                return searched_nodes + 1;
+        self.count_heads.entry(count + 1).or_insert(slot);
+        AccessCost::moved(index + 1, index, index - distance)
+    }
+
+    /// Accesses a value in a BIT list. Complements the value's bit and moves it to the front
+    /// if the bit became 1. New values are appended with a uniformly random bit.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to access.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _bit_access(&mut self, value: u32) -> AccessCost {
+        let (bits, rng) = match &mut self.list_type {
+            ListType::Bit(bits, rng) => (bits, rng),
+            _ => panic!("Invalid list type"),
+        };
+        match self.slots.get(&value) {
+            None => {
+                bits.insert(value, rng.random_bool(0.5));
+                AccessCost::searched(self._push_back(value).1)
+            }
+            Some(&slot) => {
+                let bit = bits.get_mut(&value).unwrap();
+                *bit = !*bit;
+                let moved = *bit;
+                self._move_to_front_if(slot, moved)
             }
-            searched_nodes += 1;
-            current = &mut current.as_mut().unwrap().next;
         }
-        let new_node = Box::new(Node { value, next: None });
-        current.as_mut().unwrap().next = Some(new_node);
-        counts.insert(value, 1);
-        searched_nodes
     }
 
-    /// Reorders the list based on descending access counts.
-    fn _reorder_by_count(&mut self) {
-        let counts = match &mut self.list_type {
-            ListType::Count(counts) => counts,
+    /// Accesses a value in a Move-Every-Other list. Moves the value to the front on its
+    /// first, third, fifth, ... access after insertion.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to access.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _move_every_other_access(&mut self, value: u32) -> AccessCost {
+        let bits = match &mut self.list_type {
+            ListType::MoveEveryOther(bits) => bits,
             _ => panic!("Invalid list type"),
         };
-        let mut swapped;
-        loop {
-            swapped = false;
-            let mut current = &mut self.head;
-            while let Some(node) = current.as_ref().unwrap().next.as_ref() {
-                let current_value = current.as_ref().unwrap().value;
-                let next_value = node.value;
-                if counts[&current_value] < counts[&next_value] {
-                    let mut found_node = current.as_mut().unwrap().next.take();
-                    let temp = current.take();
-                    if let Some(mut temp_node) = temp {
-                        temp_node.next = found_node.as_mut().unwrap().next.take();
-                        found_node.as_mut().unwrap().next = Some(temp_node);
-                    }
-                    *current = Some(found_node.unwrap());
-                    swapped = true;
-                }
-                current = &mut current.as_mut().unwrap().next;
+        match self.slots.get(&value) {
+            None => {
+                bits.insert(value, false);
+                AccessCost::searched(self._push_back(value).1)
+            }
+            Some(&slot) => {
+                let bit = bits.get_mut(&value).unwrap();
+                *bit = !*bit;
+                let moved = *bit;
+                self._move_to_front_if(slot, moved)
+            }
+        }
+    }
+
+    /// Charges the search for a node found in the list and moves it to the front if requested.
+    fn _move_to_front_if(&mut self, slot: usize, moved: bool) -> AccessCost {
+        let index = self._index_of(slot) as u32;
+        if !moved {
+            return AccessCost::searched(index + 1);
+        }
+        self._move_before(slot, self.head.unwrap());
+        AccessCost::moved(index + 1, index, 0)
+    }
+
+    /// Accesses a value in a TIMESTAMP list. Inserts the value in front of the first
+    /// preceding value that was requested at most once since the last request to it.
+    /// Values requested for the first time are appended and not moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to access.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _timestamp_access(&mut self, value: u32) -> AccessCost {
+        let time = self.clock;
+        let times = match &mut self.list_type {
+            ListType::Timestamp(times) => times,
+            _ => panic!("Invalid list type"),
+        };
+        let Some(&slot) = self.slots.get(&value) else {
+            times.insert(value, (time, None));
+            return AccessCost::searched(self._push_back(value).1);
+        };
+
+        let last = times[&value].0;
+        let mut target = None;
+        let mut index = 0;
+        let mut current = self.head.unwrap();
+        while current != slot {
+            let node = &self.nodes[current];
+            if target.is_none()
+                && times[&node.value].1.is_none_or(|second_last| second_last < last)
+            {
+                target = Some((current, index));
+            }
+            index += 1;
+            current = node.next.unwrap();
+        }
+        times.insert(value, (time, Some(last)));
+        let new_index = match target {
+            Some((before, target_index)) => {
+                self._move_before(slot, before);
+                target_index
+            }
+            None => index,
+        };
+        AccessCost::moved(index + 1, index, new_index)
+    }
+
+    /// Accesses a value in a decaying Count list. Adds the access to the value's decayed
+    /// count and moves it in front of all preceding values with a smaller count.
+    ///
+    /// Instead of decaying every count on each access, an access at time `t` adds
+    /// `decay^-t` to the value's score, which preserves the order of the decayed counts.
+    /// Scores are kept as logarithms to avoid overflow.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to access.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _count_decay_access(&mut self, value: u32) -> AccessCost {
+        let time = self.clock;
+        // A new value is appended after the nodes searched for it, at the index of their count.
+        let (slot, index, searched_nodes) = match self.slots.get(&value) {
+            Some(&slot) => {
+                let index = self._index_of(slot) as u32;
+                (slot, index, index + 1)
+            }
+            None => {
+                let (slot, searched_nodes) = self._push_back(value);
+                (slot, searched_nodes, searched_nodes)
             }
-            if !swapped {
+        };
+        let (scores, decay) = match &mut self.list_type {
+            ListType::CountDecay(scores, decay) => (scores, *decay),
+            _ => panic!("Invalid list type"),
+        };
+        let increment = -(time as f64) * decay.ln();
+        let score = match scores.get(&value) {
+            // log(e^a + e^b), computed without overflow
+            Some(&old) => old.max(increment) + (-(old - increment).abs()).exp().ln_1p(),
+            None => increment,
+        };
+        scores.insert(value, score);
+
+        let mut target = slot;
+        let mut new_index = index;
+        while let Some(prev) = self.nodes[target].prev {
+            if scores[&self.nodes[prev].value] >= score {
                 break;
             }
+            target = prev;
+            new_index -= 1;
+        }
+        self._move_before(slot, target);
+        AccessCost::moved(searched_nodes, index, new_index)
+    }
+}
+
+/// Serves requests of the dynamic list update problem, charged by the list's cost model.
+impl OnlineAlgorithm<Request> for LinkedList {
+    fn serve(&mut self, request: Request) -> u64 {
+        LinkedList::serve(self, request).total(self.cost_model) as u64
+    }
+
+    /// Empties the list and forgets the access history, keeping the random number generator
+    /// of a Bit list, the decay factor of a CountDecay list and the cost model.
+    fn reset(&mut self) {
+        self.nodes.clear();
+        self.free_slots.clear();
+        self.slots.clear();
+        self.head = None;
+        self.tail = None;
+        self.len = 0;
+        self.count_heads.clear();
+        self.clock = 0;
+        match &mut self.list_type {
+            ListType::Simple | ListType::MoveToFront | ListType::Transpose => {}
+            ListType::Count(counts) => counts.clear(),
+            ListType::Bit(bits, _) | ListType::MoveEveryOther(bits) => bits.clear(),
+            ListType::Timestamp(times) => times.clear(),
+            ListType::CountDecay(scores, _) => scores.clear(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.list_type.name()
+    }
+
+    /// Hashes the values in list order, so lists holding the same values in different orders
+    /// differ. An empty list hashes to 0.
+    fn snapshot(&self) -> u64 {
+        let mut hash = 0;
+        let mut current = self.head;
+        while let Some(slot) = current {
+            let node = &self.nodes[slot];
+            hash = splitmix64(hash ^ node.value as u64);
+            current = node.next;
         }
+        hash
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
 
     #[test]
     fn test_simple_access() {
         let mut list = LinkedList::new(ListType::Simple);
         let access_data = [(1, 0), (2, 1), (3, 2), (3, 3)];
         for (value, expected) in access_data {
-            assert_eq!(list.access(value), expected);
+            assert_eq!(list.access(value).search, expected);
         }
         let pop_data: [Option<u32>; 4] = [Some(1), Some(2), Some(3), None];
         for expected in pop_data {
@@ -307,7 +778,7 @@ mod tests {
         let mut list = LinkedList::new(ListType::MoveToFront);
         let access_data = [(1, 0), (2, 1), (3, 2), (1, 1), (3, 3), (3, 1), (3, 1), (2, 3), (3, 2)];
         for (value, expected) in access_data {
-            assert_eq!(list.access(value), expected);
+            assert_eq!(list.access(value).search, expected);
         }
         let pop_data: [Option<u32>; 4] = [Some(3), Some(2), Some(1), None];
         for expected in pop_data {
@@ -320,7 +791,7 @@ mod tests {
         let mut list = LinkedList::new(ListType::Transpose);
         let access_data = [(1, 0), (2, 1), (3, 2), (1, 1), (3, 3), (3, 2)];
         for (value, expected) in access_data {
-            assert_eq!(list.access(value), expected);
+            assert_eq!(list.access(value).search, expected);
         }
         let pop_data: [Option<u32>; 4] = [Some(3), Some(1), Some(2), None];
         for expected in pop_data {
@@ -333,11 +804,216 @@ mod tests {
         let mut list = LinkedList::new(ListType::Count(HashMap::new()));
         let access_data = [(1, 0), (2, 1), (3, 2), (1, 1), (3, 3), (3, 2)];
         for (value, expected) in access_data {
-            assert_eq!(list.access(value), expected);
+            assert_eq!(list.access(value).search, expected);
+        }
+        let pop_data: [Option<u32>; 4] = [Some(3), Some(1), Some(2), None];
+        for expected in pop_data {
+            assert_eq!(list.pop(), expected);
+        }
+    }
+
+    #[test]
+    fn test_bit_access() {
+        let mut list = LinkedList::new(ListType::Bit(HashMap::new(), StdRng::seed_from_u64(0)));
+        let access_data = [(1, 0), (2, 1), (3, 2), (3, 3)];
+        for (value, expected) in access_data {
+            assert_eq!(list.access(value).search, expected);
+        }
+        // Depending on the initial bit, 3 moved to the front on the first or the second access.
+        assert!([1, 3].contains(&list.access(3).search));
+        let pop_data: [Option<u32>; 4] = [Some(3), Some(1), Some(2), None];
+        for expected in pop_data {
+            assert_eq!(list.pop(), expected);
+        }
+    }
+
+    #[test]
+    fn test_move_every_other_access() {
+        let mut list = LinkedList::new(ListType::MoveEveryOther(HashMap::new()));
+        let access_data = [(1, 0), (2, 1), (3, 2), (3, 3), (3, 1), (2, 3), (1, 3), (3, 3)];
+        for (value, expected) in access_data {
+            assert_eq!(list.access(value).search, expected);
         }
         let pop_data: [Option<u32>; 4] = [Some(3), Some(1), Some(2), None];
         for expected in pop_data {
             assert_eq!(list.pop(), expected);
         }
     }
+
+    #[test]
+    fn test_timestamp_access() {
+        let mut list = LinkedList::new(ListType::Timestamp(HashMap::new()));
+        let access_data = [(1, 0), (2, 1), (3, 2), (3, 3), (2, 3), (1, 3), (1, 3)];
+        for (value, expected) in access_data {
+            assert_eq!(list.access(value).search, expected);
+        }
+        let pop_data: [Option<u32>; 4] = [Some(1), Some(3), Some(2), None];
+        for expected in pop_data {
+            assert_eq!(list.pop(), expected);
+        }
+    }
+
+    #[test]
+    fn test_count_decay_access() {
+        let mut list = LinkedList::new(ListType::CountDecay(HashMap::new(), 0.9));
+        let access_data = [(1, 0), (1, 1), (1, 1), (2, 1), (2, 2), (2, 2), (1, 2)];
+        for (value, expected) in access_data {
+            assert_eq!(list.access(value).search, expected);
+        }
+        let pop_data: [Option<u32>; 3] = [Some(1), Some(2), None];
+        for expected in pop_data {
+            assert_eq!(list.pop(), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Decay factor must lie in (0, 1]")]
+    fn test_count_decay_factor() {
+        LinkedList::new(ListType::CountDecay(HashMap::new(), f64::NAN));
+    }
+
+    #[test]
+    fn test_access_cost() {
+        let mut list = LinkedList::new(ListType::MoveToFront);
+        for value in [1, 2, 3] {
+            list.access(value);
+        }
+        let cost = list.access(3);
+        assert_eq!(
+            cost,
+            AccessCost {
+                search: 3,
+                free_exchanges: 2,
+                paid_exchanges: 0
+            }
+        );
+        assert_eq!(cost.total(CostModel::SLEATOR_TARJAN), 3);
+        assert_eq!(cost.total(CostModel::SEARCH_ONLY), 3);
+        let charged = CostModel {
+            free_exchange: 1,
+            paid_exchange: 1,
+        };
+        assert_eq!(cost.total(charged), 5);
+    }
+
+    /// Count only moves the accessed element toward the front, so the standard model charges
+    /// nothing for its reorganisation; moving an element toward the back would be paid.
+    #[test]
+    fn test_count_exchanges() {
+        let mut list = LinkedList::new(ListType::Count(HashMap::new()));
+        for value in [1, 2, 3] {
+            list.access(value);
+        }
+        let access_data = [(3, 3, 2), (3, 1, 0), (2, 3, 1)];
+        for (value, search, free_exchanges) in access_data {
+            let cost = list.access(value);
+            assert_eq!(
+                cost,
+                AccessCost {
+                    search,
+                    free_exchanges,
+                    paid_exchanges: 0
+                }
+            );
+            assert_eq!(cost.total(CostModel::SLEATOR_TARJAN), search);
+        }
+        let backward = AccessCost::moved(2, 1, 3);
+        assert_eq!(backward.paid_exchanges, 2);
+        assert_eq!(backward.total(CostModel::SLEATOR_TARJAN), 4);
+        assert_eq!(backward.total(CostModel::SEARCH_ONLY), 2);
+    }
+
+    #[test]
+    fn test_set_cost_model() {
+        let requests = [1, 2, 3, 3, 1].map(Request::Access);
+        let charged = CostModel {
+            free_exchange: 1,
+            paid_exchange: 1,
+        };
+        let mut reference = LinkedList::new(ListType::MoveToFront);
+        let costs: Vec<AccessCost> = requests.iter().map(|&r| reference.serve(r)).collect();
+
+        let mut list = LinkedList::new(ListType::MoveToFront);
+        let total = |model: CostModel| -> u64 {
+            costs.iter().map(|cost| cost.total(model) as u64).sum()
+        };
+        assert_eq!(online_core::run(&mut list, requests), total(CostModel::SLEATOR_TARJAN));
+        // The cost model survives a reset.
+        list.set_cost_model(charged);
+        list.reset();
+        assert_eq!(online_core::run(&mut list, requests), total(charged));
+        assert!(total(charged) > total(CostModel::SLEATOR_TARJAN));
+    }
+
+    #[test]
+    fn test_insert_and_delete() {
+        let mut list = LinkedList::new(ListType::MoveToFront);
+        let request_data = [
+            (Request::Access(1), 0),
+            (Request::Access(2), 1),
+            (Request::Access(3), 2),
+            (Request::Delete(2), 2),
+            (Request::Insert(4), 2),
+            (Request::Insert(3), 2),
+            (Request::Access(3), 2),
+            (Request::Delete(5), 3),
+        ];
+        for (request, expected) in request_data {
+            assert_eq!(list.serve(request).search, expected);
+        }
+        let pop_data: [Option<u32>; 4] = [Some(3), Some(1), Some(4), None];
+        for expected in pop_data {
+            assert_eq!(list.pop(), expected);
+        }
+    }
+
+    #[test]
+    fn test_count_delete() {
+        let mut list = LinkedList::new(ListType::Count(HashMap::new()));
+        // Deleting the first node with count 1 and forgetting the count of a deleted value.
+        let request_data = [
+            (Request::Access(1), 0),
+            (Request::Access(2), 1),
+            (Request::Access(3), 2),
+            (Request::Delete(1), 1),
+            (Request::Access(3), 2),
+            (Request::Access(3), 1),
+            (Request::Delete(3), 1),
+            (Request::Insert(3), 1),
+            (Request::Access(3), 2),
+        ];
+        for (request, expected) in request_data {
+            assert_eq!(list.serve(request).search, expected);
+        }
+        let pop_data: [Option<u32>; 3] = [Some(3), Some(2), None];
+        for expected in pop_data {
+            assert_eq!(list.pop(), expected);
+        }
+    }
+
+    #[test]
+    fn test_online_algorithm() {
+        let requests: Vec<Request> = [1, 2, 3, 2, 1, 3, 3, 4, 1]
+            .into_iter()
+            .map(Request::Access)
+            .collect();
+        let mut list = LinkedList::new(ListType::MoveToFront);
+        assert_eq!(OnlineAlgorithm::name(&list), "MoveToFront");
+        let cost = online_core::run(&mut list, requests.iter().copied());
+        let snapshot = OnlineAlgorithm::snapshot(&list);
+        assert_eq!(list.pop(), Some(1));
+        assert_ne!(OnlineAlgorithm::snapshot(&list), snapshot);
+
+        // A reset list serves the sequence as a new one does.
+        list.reset();
+        assert_eq!(OnlineAlgorithm::snapshot(&list), 0);
+        let mut fresh = LinkedList::new(ListType::MoveToFront);
+        assert_eq!(online_core::run(&mut list, requests.iter().copied()), cost);
+        assert_eq!(online_core::run(&mut fresh, requests.iter().copied()), cost);
+        assert_eq!(
+            OnlineAlgorithm::snapshot(&list),
+            OnlineAlgorithm::snapshot(&fresh)
+        );
+        assert_eq!(OnlineAlgorithm::snapshot(&list), snapshot);
+    }
 }
@@ -1,28 +1,110 @@
 mod linked_list;
-use linked_list::{LinkedList, ListType};
+use linked_list::{CostModel, LinkedList, ListType, Request};
 
-mod sampler;
-use sampler::{DistributionType, RandomSampler};
+mod opt;
 
+use online_core::{OnlineAlgorithm, compare, run};
+use online_core::results::ResultWriter;
+use online_core::sampler::{DistributionType, RandomSampler};
+use online_core::seed::{derive_seed, master_seed};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, Write};
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::io;
 
 const SAMPLE_SIZE: usize = 1000;
+/// The sizes of the alphabets requests are drawn from: values 1 to `values`. OPT is exact
+/// on the small alphabet; on the large one only its lower bound is known, so the ratios
+/// there overstate the competitive ratio. Under the search-only model OPT is exact on both.
+const VALUES: [usize; 2] = [opt::MAX_EXACT_ITEMS, 100];
+/// The longest static sequence on the small alphabet under the Sleator–Tarjan model, as exact
+/// OPT costs a search over all 720 lists per request.
+const MAX_EXACT_N: usize = 10_000;
+/// The probability that a drawn value already in the list is deleted rather than accessed in
+/// the dynamic workload.
+const DELETE_PROBABILITY: f64 = 0.1;
+
+const USAGE: &str = "Usage:
+    l1                  Run the sweep, charging paid exchanges by the Sleator–Tarjan model
+    l1 search-only      Run the sweep, charging only the search as if reorganising were free";
+
+type ListTypeFactory = Box<dyn Fn(u64) -> ListType + Sync>;
+
+/// The kinds of request sequences the lists serve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Workload {
+    /// Accesses to the drawn values, for which OPT is known.
+    Static,
+    /// A dynamic dictionary: drawn values are inserted when missing, and otherwise deleted
+    /// with probability `DELETE_PROBABILITY` or accessed. OPT is unknown, so no ratio is
+    /// reported.
+    Dynamic,
+}
+
+/// Turns the drawn values into the requests of a dynamic dictionary.
+///
+/// # Arguments
+///
+/// * `values` - The drawn values.
+/// * `rng` - The random number generator deciding between deletions and accesses.
+///
+/// # Returns
+///
+/// One insertion, deletion or access per drawn value.
+fn dynamic_requests(values: &[u32], rng: &mut StdRng) -> Vec<Request> {
+    let mut present = HashSet::new();
+    values
+        .iter()
+        .map(|&value| {
+            if present.insert(value) {
+                Request::Insert(value)
+            } else if rng.random::<f64>() < DELETE_PROBABILITY {
+                present.remove(&value);
+                Request::Delete(value)
+            } else {
+                Request::Access(value)
+            }
+        })
+        .collect()
+}
 
 fn main() -> io::Result<()> {
-    let file = File::create("l1.csv")?;
-    let file = Mutex::new(file); // Wrap the file in a Mutex for synchronized access
-    writeln!(file.lock().unwrap(), "n;list_type;distribution;total_cost")?;
+    let seed = master_seed();
+    println!("Master seed: {}", seed);
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (cost_model_name, cost_model) = match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [] => ("SleatorTarjan", CostModel::SLEATOR_TARJAN),
+        ["search-only"] => ("SearchOnly", CostModel::SEARCH_ONLY),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let file = ResultWriter::create(
+        "l1.csv",
+        "cost_model;workload;n;values;list_type;distribution;seed;total_cost;opt_cost;opt_exact;competitive_ratio",
+    )?;
 
     let ns = [100, 500, 1000, 5000, 10_000, 50_000, 100_000];
-    let list_types = [
-        ListType::Simple,
-        ListType::MoveToFront,
-        ListType::Transpose,
-        ListType::Count(HashMap::new()),
+    // Define list types as factory functions producing a new variant from a seed.
+    let list_types: Vec<ListTypeFactory> = vec![
+        Box::new(|_| ListType::Simple),
+        Box::new(|_| ListType::MoveToFront),
+        Box::new(|_| ListType::Transpose),
+        Box::new(|_| ListType::Count(HashMap::new())),
+        Box::new(|seed| ListType::Bit(HashMap::new(), StdRng::seed_from_u64(seed))),
+        Box::new(|_| ListType::MoveEveryOther(HashMap::new())),
+        Box::new(|_| ListType::Timestamp(HashMap::new())),
+        Box::new(|_| ListType::CountDecay(HashMap::new(), 0.99)),
     ];
     let distribution_types = [
         DistributionType::Uniform,
@@ -31,33 +113,105 @@ fn main() -> io::Result<()> {
         DistributionType::Geometric,
     ];
 
-    ns.par_iter().for_each(|&n| {
-        list_types.par_iter().for_each(|list_type| {
-            distribution_types.par_iter().for_each(|distribution_type| {
-                let mut sampler = RandomSampler::new(*distribution_type);
+    let sweeps: Vec<(Workload, usize, usize)> = [Workload::Static, Workload::Dynamic]
+        .into_iter()
+        .flat_map(|workload| VALUES.map(|values| (workload, values)))
+        .flat_map(|(workload, values)| ns.map(|n| (workload, values, n)))
+        .filter(|&(workload, values, n)| {
+            workload != Workload::Static
+                || values != opt::MAX_EXACT_ITEMS
+                || n <= MAX_EXACT_N
+                || cost_model == CostModel::SEARCH_ONLY
+        })
+        .collect();
+    sweeps.par_iter().for_each(|&(workload, values, n)| {
+        distribution_types.par_iter().enumerate().for_each(
+            |(distribution_index, distribution_type)| {
                 println!(
-                    "List type: {:?}, Distribution type: {:?}, n: {}",
-                    list_type, distribution_type, n
+                    "Workload: {:?}, distribution type: {:?}, n: {}, values: {}",
+                    workload, distribution_type, n, values
                 );
                 let mut results = Vec::new();
-                for _ in 0..SAMPLE_SIZE {
-                    let mut list = LinkedList::new(list_type.clone());
-                    let mut total_cost = 0;
-                    for _ in 0..n {
-                        total_cost += list.access(sampler.sample());
+                for sample in 0..SAMPLE_SIZE {
+                    // Each row can be regenerated from its seed alone.
+                    let row_seed = derive_seed(
+                        seed,
+                        &[
+                            workload as u64,
+                            values as u64,
+                            n as u64,
+                            distribution_index as u64,
+                            sample as u64,
+                        ],
+                    );
+                    let mut sampler = RandomSampler::new(*distribution_type, values, row_seed);
+                    // Every list type serves the same sequence, so they share one OPT.
+                    let mut requests = vec![0; n];
+                    sampler.sample_into(&mut requests);
+                    let opt = match workload {
+                        Workload::Static => Some(opt::offline_cost(&requests, cost_model)),
+                        Workload::Dynamic => None,
+                    };
+                    let mut lists: Vec<LinkedList> = list_types
+                        .iter()
+                        .enumerate()
+                        .map(|(list_index, list_factory)| {
+                            let mut list = LinkedList::new(list_factory(derive_seed(
+                                row_seed,
+                                &[0, list_index as u64],
+                            )));
+                            list.set_cost_model(cost_model);
+                            list
+                        })
+                        .collect();
+                    let requests: Vec<Request> = match workload {
+                        Workload::Static => requests.into_iter().map(Request::Access).collect(),
+                        Workload::Dynamic => dynamic_requests(
+                            &requests,
+                            &mut StdRng::seed_from_u64(derive_seed(row_seed, &[1])),
+                        ),
+                    };
+                    let row = |name: &str, cost: u64, opt_cost: &dyn Display, opt_exact, ratio| {
+                        format!(
+                            "{};{:?};{};{};{};{:?};{};{};{};{};{}",
+                            cost_model_name,
+                            workload,
+                            n,
+                            values,
+                            name,
+                            distribution_type,
+                            row_seed,
+                            cost,
+                            opt_cost,
+                            opt_exact,
+                            ratio
+                        )
+                    };
+                    match opt {
+                        Some((opt_cost, opt_exact)) => {
+                            for report in compare(&mut lists, &requests, opt_cost) {
+                                results.push(row(
+                                    &report.name,
+                                    report.cost,
+                                    &opt_cost,
+                                    opt_exact,
+                                    report.competitive_ratio(),
+                                ));
+                            }
+                        }
+                        // Without an OPT there is nothing to compare to, so the lists only run.
+                        None => {
+                            for list in &mut lists {
+                                let cost = run(list, requests.iter().copied());
+                                results.push(row(list.name(), cost, &"NaN", false, f64::NAN));
+                            }
+                        }
                     }
-                    results.push(format!(
-                        "{};{:?};{:?};{}",
-                        n, list_type, distribution_type, total_cost
-                    ));
-                }
-                let mut file = file.lock().unwrap(); // Lock the file for writing
-                for result in results {
-                    writeln!(file, "{}", result).expect("Failed to write to file");
                 }
-            });
-        });
+                file.write_rows(results).expect("Failed to write to file");
+            },
+        );
     });
 
-    Ok(())
+    file.finish()
 }
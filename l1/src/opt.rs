@@ -0,0 +1,374 @@
+use crate::linked_list::CostModel;
+use std::collections::{HashMap, HashSet};
+
+/// The largest number of distinct items for which the exact solver is used (6! = 720 states).
+pub const MAX_EXACT_ITEMS: usize = 6;
+
+/// All permutations of the items `0..len`, together with the adjacency of the
+/// permutation graph in which two lists are connected by a single transposition
+/// of neighbouring items.
+struct Level {
+    /// Every permutation of `0..len`.
+    perms: Vec<Vec<u8>>,
+    /// `neighbours[s]` lists the permutations reachable from `perms[s]` by one exchange.
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Level {
+    /// Creates the level for lists of length 0.
+    fn empty() -> Self {
+        Level {
+            perms: vec![Vec::new()],
+            neighbours: vec![Vec::new()],
+        }
+    }
+
+    /// Builds the level for lists that are one item longer.
+    ///
+    /// The permutations are laid out so that inserting the new item at `position` of
+    /// `perms[s]` gives the permutation at index `s * (len + 1) + position`.
+    fn extend(&self) -> Self {
+        let item = self.perms[0].len() as u8;
+        let mut perms = Vec::with_capacity(self.perms.len() * (item as usize + 1));
+        for perm in &self.perms {
+            for position in 0..=perm.len() {
+                let mut extended = perm.clone();
+                extended.insert(position, item);
+                perms.push(extended);
+            }
+        }
+        let index: HashMap<&[u8], usize> = perms
+            .iter()
+            .enumerate()
+            .map(|(s, perm)| (perm.as_slice(), s))
+            .collect();
+        let neighbours = perms
+            .iter()
+            .map(|perm| {
+                (1..perm.len())
+                    .map(|i| {
+                        let mut swapped = perm.clone();
+                        swapped.swap(i - 1, i);
+                        index[swapped.as_slice()]
+                    })
+                    .collect()
+            })
+            .collect();
+        Level { perms, neighbours }
+    }
+
+    /// Returns the index in the next level of `perms[s]` with the new item inserted at
+    /// `position`.
+    fn insert_index(&self, s: usize, position: usize) -> usize {
+        let len = self.perms[0].len();
+        s * (len + 1) + position
+    }
+}
+
+/// Lowers every cost in `dp` to the cheapest way of reaching that list with paid exchanges.
+///
+/// Runs a multi-source breadth-first search over the permutation graph, where each edge
+/// is one paid exchange of cost 1. The lists are visited in order of cost from one bucket
+/// per cost, starting at the cheapest list.
+///
+/// # Arguments
+///
+/// * `dp` - The cost of ending with each list, `u64::MAX` if unreachable.
+/// * `level` - The permutation graph.
+/// * `buckets` - Scratch space, reused across calls to avoid allocating.
+fn relax(dp: &mut [u64], level: &Level, buckets: &mut Vec<Vec<usize>>) {
+    let Some(min) = dp.iter().copied().filter(|&cost| cost != u64::MAX).min() else {
+        return;
+    };
+    // buckets[d] holds the lists reached at cost min + d.
+    for (s, &cost) in dp.iter().enumerate() {
+        if cost != u64::MAX {
+            let d = (cost - min) as usize;
+            if buckets.len() <= d {
+                buckets.resize_with(d + 1, Vec::new);
+            }
+            buckets[d].push(s);
+        }
+    }
+    let mut d = 0;
+    while d < buckets.len() {
+        let cost = min + d as u64;
+        let mut bucket = std::mem::take(&mut buckets[d]);
+        for &s in &bucket {
+            if dp[s] != cost {
+                continue;
+            }
+            for &t in &level.neighbours[s] {
+                if cost + 1 < dp[t] {
+                    dp[t] = cost + 1;
+                    if buckets.len() == d + 1 {
+                        buckets.push(Vec::new());
+                    }
+                    buckets[d + 1].push(t);
+                }
+            }
+        }
+        bucket.clear();
+        buckets[d] = bucket;
+        d += 1;
+    }
+}
+
+/// Computes the exact cost of an optimal offline algorithm for the request sequence.
+///
+/// Uses the same cost model as `LinkedList::access`: the list starts empty, the first
+/// request to an item costs the current length of the list and inserts it anywhere, as
+/// the new item may move forward for free, and a request to the item at position `i`
+/// (1-based) costs `i`. Before each request the algorithm may perform paid exchanges of
+/// neighbouring items at cost 1 each. Free exchanges after an access are not needed, as
+/// there is always an optimal offline algorithm that serves accesses with only paid
+/// exchanges (Reingold & Westbrook).
+///
+/// # Arguments
+///
+/// * `requests` - The request sequence.
+///
+/// # Returns
+///
+/// The optimal total cost.
+///
+/// # Panics
+///
+/// Panics if the sequence contains more than `MAX_EXACT_ITEMS` distinct items.
+pub fn exact_cost(requests: &[u32]) -> u64 {
+    let mut items: HashMap<u32, u8> = HashMap::new();
+    let mut level = Level::empty();
+    // dp[s] is the cheapest cost of serving the prefix and ending with the list perms[s].
+    let mut dp = vec![0];
+    let mut buckets = Vec::new();
+
+    for &value in requests {
+        match items.get(&value) {
+            Some(&item) => {
+                relax(&mut dp, &level, &mut buckets);
+                for (cost, perm) in dp.iter_mut().zip(&level.perms) {
+                    let position = perm.iter().position(|&x| x == item).unwrap();
+                    *cost += position as u64 + 1;
+                }
+            }
+            None => {
+                assert!(
+                    items.len() < MAX_EXACT_ITEMS,
+                    "Too many distinct items for the exact solver"
+                );
+                let len = items.len() as u64;
+                items.insert(value, items.len() as u8);
+                let next = level.extend();
+                let mut next_dp = vec![u64::MAX; next.perms.len()];
+                for (s, &cost) in dp.iter().enumerate() {
+                    if cost != u64::MAX {
+                        for position in 0..=len as usize {
+                            next_dp[level.insert_index(s, position)] = cost + len;
+                        }
+                    }
+                }
+                level = next;
+                dp = next_dp;
+            }
+        }
+    }
+
+    dp.into_iter().min().unwrap()
+}
+
+/// Computes a lower bound on the optimal offline cost, feasible for any alphabet size.
+///
+/// The cost of any list algorithm splits into one unit per repeated request plus, for
+/// every pair of items, the requests where the other item of the pair was in front and
+/// the exchanges between the two. Summing the optimal cost of every two-item projection
+/// therefore never exceeds the optimum for the whole list.
+///
+/// # Arguments
+///
+/// * `requests` - The request sequence.
+///
+/// # Returns
+///
+/// A lower bound on the optimal total cost.
+pub fn lower_bound(requests: &[u32]) -> u64 {
+    // Positions of the requests to each item, items numbered in order of first appearance.
+    let mut items: HashMap<u32, usize> = HashMap::new();
+    let mut occurrences: Vec<Vec<usize>> = Vec::new();
+    for (t, &value) in requests.iter().enumerate() {
+        let item = *items.entry(value).or_insert_with(|| {
+            occurrences.push(Vec::new());
+            occurrences.len() - 1
+        });
+        occurrences[item].push(t);
+    }
+
+    let mut bound = (requests.len() - occurrences.len()) as u64;
+    for (y, later) in occurrences.iter().enumerate() {
+        for earlier in &occurrences[..y] {
+            // x is already in the list when y is inserted, on either side of it.
+            let start = earlier.partition_point(|&t| t < later[0]);
+            let (mut x_front, mut y_front) = (1, 1);
+            let (mut i, mut j) = (start, 1);
+            while i < earlier.len() || j < later.len() {
+                let x_next = i < earlier.len() && (j == later.len() || earlier[i] < later[j]);
+                (x_front, y_front) = (
+                    x_front.min(y_front + 1),
+                    y_front.min(x_front + 1),
+                );
+                if x_next {
+                    y_front += 1;
+                    i += 1;
+                } else {
+                    x_front += 1;
+                    j += 1;
+                }
+            }
+            bound += x_front.min(y_front);
+        }
+    }
+    bound
+}
+
+/// Computes the exact optimal offline cost when reorganising the list is free.
+///
+/// The first request to an item costs the current length of the list, and every later
+/// request costs 1, as the item can be moved to the front for free beforehand.
+///
+/// # Arguments
+///
+/// * `requests` - The request sequence.
+///
+/// # Returns
+///
+/// The optimal total cost.
+pub fn search_only_cost(requests: &[u32]) -> u64 {
+    let mut items = HashSet::new();
+    requests
+        .iter()
+        .map(|&value| {
+            let len = items.len() as u64;
+            if items.insert(value) { len } else { 1 }
+        })
+        .sum()
+}
+
+/// Computes the optimal offline cost under the given cost model: exactly when reorganising
+/// is free or the alphabet is small enough, and as a lower bound otherwise.
+///
+/// # Arguments
+///
+/// * `requests` - The request sequence.
+/// * `cost_model` - The cost model, `SLEATOR_TARJAN` or `SEARCH_ONLY`.
+///
+/// # Returns
+///
+/// `search_only_cost` under the search-only model, and under the Sleator–Tarjan model
+/// `exact_cost` for at most `MAX_EXACT_ITEMS` distinct items and `lower_bound` otherwise,
+/// together with whether the cost is exact.
+///
+/// # Panics
+///
+/// Panics if the cost model is neither of the two.
+pub fn offline_cost(requests: &[u32], cost_model: CostModel) -> (u64, bool) {
+    if cost_model == CostModel::SEARCH_ONLY {
+        return (search_only_cost(requests), true);
+    }
+    assert_eq!(
+        cost_model,
+        CostModel::SLEATOR_TARJAN,
+        "No offline solver for the cost model"
+    );
+    let mut distinct: Vec<u32> = requests.to_vec();
+    distinct.sort_unstable();
+    distinct.dedup();
+    if distinct.len() <= MAX_EXACT_ITEMS {
+        (exact_cost(requests), true)
+    } else {
+        (lower_bound(requests), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_list::{LinkedList, ListType};
+
+    /// A fixed, irregular request sequence over `items` items.
+    fn sequence(items: u32, len: usize) -> Vec<u32> {
+        (0..len as u32).map(|i| (i * i * 7 + i * 3 + 1) % items + 1).collect()
+    }
+
+    #[test]
+    fn test_exact_cost() {
+        let cases: [(&[u32], u64); 4] = [
+            (&[], 0),
+            (&[1, 1, 1], 2),
+            (&[1, 2, 1, 2], 4),
+            (&[1, 2, 2, 2], 3),
+        ];
+        for (requests, expected) in cases {
+            assert_eq!(exact_cost(requests), expected, "Mismatch for {:?}", requests);
+        }
+    }
+
+    #[test]
+    fn test_exact_cost_not_above_online() {
+        // CountDecay moves the inserted 2 to the front for free and pays 3 on the short one.
+        for requests in [sequence(5, 200), vec![1, 2, 2, 2]] {
+            let opt = exact_cost(&requests);
+            for list_type in [
+                ListType::Simple,
+                ListType::MoveToFront,
+                ListType::Transpose,
+                ListType::Count(HashMap::new()),
+                ListType::MoveEveryOther(HashMap::new()),
+                ListType::Timestamp(HashMap::new()),
+                ListType::CountDecay(HashMap::new(), 0.9),
+                ListType::CountDecay(HashMap::new(), 0.99),
+            ] {
+                let mut list = LinkedList::new(list_type.clone());
+                let cost: u64 = requests
+                    .iter()
+                    .map(|&value| list.access(value).total(CostModel::SLEATOR_TARJAN) as u64)
+                    .sum();
+                assert!(opt <= cost, "{:?} beat OPT: {} < {}", list_type, cost, opt);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        // With two items the pairwise bound is exact.
+        let requests = sequence(2, 50);
+        assert_eq!(lower_bound(&requests), exact_cost(&requests));
+        for items in 3..=6 {
+            let requests = sequence(items, 100);
+            assert!(lower_bound(&requests) <= exact_cost(&requests));
+        }
+    }
+
+    #[test]
+    fn test_offline_cost() {
+        let requests = sequence(MAX_EXACT_ITEMS as u32, 100);
+        assert_eq!(
+            offline_cost(&requests, CostModel::SLEATOR_TARJAN),
+            (exact_cost(&requests), true)
+        );
+        assert!(search_only_cost(&requests) <= exact_cost(&requests));
+        let requests = sequence(MAX_EXACT_ITEMS as u32 + 1, 100);
+        assert_eq!(
+            offline_cost(&requests, CostModel::SLEATOR_TARJAN),
+            (lower_bound(&requests), false)
+        );
+        assert_eq!(
+            offline_cost(&requests, CostModel::SEARCH_ONLY),
+            (search_only_cost(&requests), true)
+        );
+    }
+
+    #[test]
+    fn test_search_only_cost() {
+        // Inserting 1, 2 and 3 costs 0 + 1 + 2; every repeat is moved to the front first.
+        assert_eq!(search_only_cost(&[1, 2, 3, 3, 1, 2]), 6);
+    }
+}
@@ -144,4 +144,221 @@ plt.legend(title="Cache Strategy / Distribution", bbox_to_anchor=(1.05, 1), loc=
 plt.savefig(plots_dir / "avg_cost_vs_k_n2.png", dpi=300, bbox_inches="tight")
 plt.close()
 
+# ---------------------------------------------------------------------
+# Additional Plot 4: Competitive ratio against Belady's MIN on the same sequences.
+# ---------------------------------------------------------------------
+df_ratio = df[df["cache_strategy"] != "MIN"].dropna(subset=["competitive_ratio"])
+df_ratio = df_ratio.groupby(["distribution", "n", "cache_strategy", "k"], as_index=False).agg({"competitive_ratio": "mean"})
+for dist in df_ratio["distribution"].unique():
+    plt.figure(figsize=(10, 6))
+    sns.lineplot(
+        data=df_ratio[df_ratio["distribution"] == dist],
+        x="n",
+        y="competitive_ratio",
+        hue="cache_strategy",
+        marker="o",
+        palette="tab10"
+    )
+    plt.title(f"Competitive Ratio vs. Endpoint (n) for Distribution: {dist} (All k)")
+    plt.xlabel("Endpoint (n)")
+    plt.ylabel("Cost / OPT")
+    plt.legend(title="Cache Strategy")
+    plt.savefig(plots_dir / f"competitive_ratio_{dist}.png", dpi=300, bbox_inches="tight")
+    plt.close()
+
+
+# ---------------------------------------------------------------------
+# Additional Plot 5: Miss rate over time from the per-access timeline, if recorded.
+# ---------------------------------------------------------------------
+timeline_path = Path(__file__).parent / "timeline.csv"
+if timeline_path.exists():
+    df_timeline = pd.read_csv(timeline_path, delimiter=";")
+    df_timeline["miss"] = (~df_timeline["hit"]).astype(float)
+    df_timeline["miss_rate"] = df_timeline.groupby("cache_strategy")["miss"].transform(
+        lambda misses: misses.rolling(500, min_periods=1).mean()
+    )
+    plt.figure(figsize=(10, 6))
+    sns.lineplot(
+        data=df_timeline,
+        x="time",
+        y="miss_rate",
+        hue="cache_strategy",
+        palette="tab20"
+    )
+    plt.title("Miss Rate over Time (Rolling Window of 500 Requests)")
+    plt.xlabel("Request")
+    plt.ylabel("Miss Rate")
+    plt.legend(title="Cache Strategy", bbox_to_anchor=(1.05, 1), loc="upper left")
+    plt.savefig(plots_dir / "timeline_miss_rate.png", dpi=300, bbox_inches="tight")
+    plt.close()
+
+# ---------------------------------------------------------------------
+# Additional Plot 6: Resource augmentation, online cache k against MIN with cache h.
+# ---------------------------------------------------------------------
+augmentation_path = Path(__file__).parent / "augmentation_results.csv"
+if augmentation_path.exists():
+    df_aug = pd.read_csv(augmentation_path, delimiter=";")
+    df_aug = df_aug.groupby(["k", "h", "cache_strategy"], as_index=False).agg({"ratio": "max", "bound": "first"})
+    for k in sorted(df_aug["k"].unique()):
+        df_k = df_aug[df_aug["k"] == k]
+        plt.figure(figsize=(10, 6))
+        sns.lineplot(data=df_k, x="h", y="ratio", hue="cache_strategy", marker="o", palette="tab20")
+        bound = df_k.drop_duplicates("h").sort_values("h")
+        plt.plot(bound["h"], bound["bound"], "k--", label="k / (k - h + 1)")
+        plt.title(f"Worst Ratio to MIN with h Pages, Online Cache k = {k}")
+        plt.xlabel("MIN Cache Size (h)")
+        plt.ylabel("Cost / OPT(h)")
+        plt.legend(title="Cache Strategy", bbox_to_anchor=(1.05, 1), loc="upper left")
+        plt.savefig(plots_dir / f"augmentation_k{k}.png", dpi=300, bbox_inches="tight")
+        plt.close()
+
+# ---------------------------------------------------------------------
+# Additional Plot 7: Competitive ratio on the locality-of-reference request models.
+# ---------------------------------------------------------------------
+locality_path = Path(__file__).parent / "locality_results.csv"
+if locality_path.exists():
+    df_loc = pd.read_csv(locality_path, delimiter=";")
+    df_loc = df_loc[df_loc["cache_strategy"] != "MIN"]
+    df_loc = df_loc.groupby(["model", "k", "cache_strategy"], as_index=False).agg({"competitive_ratio": "mean"})
+    for model in df_loc["model"].unique():
+        plt.figure(figsize=(10, 6))
+        sns.barplot(
+            data=df_loc[df_loc["model"] == model],
+            x="k",
+            y="competitive_ratio",
+            hue="cache_strategy",
+            palette="tab20"
+        )
+        plt.title(f"Competitive Ratio by Cache Size (k) for Request Model: {model}")
+        plt.xlabel("Cache Size (k)")
+        plt.ylabel("Cost / OPT")
+        plt.legend(title="Cache Strategy", bbox_to_anchor=(1.05, 1), loc="upper left")
+        plt.savefig(plots_dir / f"locality_{model}.png", dpi=300, bbox_inches="tight")
+        plt.close()
+
+# ---------------------------------------------------------------------
+# Additional Plot 8: Miss spike and recovery time after working set shifts.
+# ---------------------------------------------------------------------
+working_set_path = Path(__file__).parent / "working_set_results.csv"
+if working_set_path.exists():
+    df_ws = pd.read_csv(working_set_path, delimiter=";")
+    df_ws = df_ws.groupby(["set_size", "overlap", "cache_strategy"], as_index=False).agg(
+        {"spike": "mean", "baseline": "mean", "recovery_time": "mean"}
+    )
+    for metric, label in [("spike", "Miss Spike Height"), ("recovery_time", "Recovery Time (Requests)")]:
+        for set_size in sorted(df_ws["set_size"].unique()):
+            plt.figure(figsize=(10, 6))
+            sns.barplot(
+                data=df_ws[df_ws["set_size"] == set_size],
+                x="overlap",
+                y=metric,
+                hue="cache_strategy",
+                palette="tab20"
+            )
+            plt.title(f"{label} after a Shift, Working Set Size = {set_size}")
+            plt.xlabel("Pages Kept at a Shift")
+            plt.ylabel(label)
+            plt.legend(title="Cache Strategy", bbox_to_anchor=(1.05, 1), loc="upper left")
+            plt.savefig(plots_dir / f"working_set_{metric}_{set_size}.png", dpi=300, bbox_inches="tight")
+            plt.close()
+
+# ---------------------------------------------------------------------
+# Additional Plot 9: Average latency of inclusive and exclusive cache hierarchies.
+# ---------------------------------------------------------------------
+hierarchy_path = Path(__file__).parent / "hierarchy_results.csv"
+if hierarchy_path.exists():
+    df_hier = pd.read_csv(hierarchy_path, delimiter=";")
+    df_hier = df_hier.groupby(["distribution", "policy", "cache_strategy"], as_index=False).agg({"avg_latency": "mean"})
+    for dist in df_hier["distribution"].unique():
+        plt.figure(figsize=(10, 6))
+        sns.barplot(
+            data=df_hier[df_hier["distribution"] == dist],
+            x="cache_strategy",
+            y="avg_latency",
+            hue="policy",
+            palette="tab10"
+        )
+        plt.title(f"Average Latency of a Two-Level Hierarchy for Distribution: {dist}")
+        plt.xlabel("Cache Strategy (Both Levels)")
+        plt.ylabel("Average Latency per Request")
+        plt.legend(title="Policy")
+        plt.savefig(plots_dir / f"hierarchy_latency_{dist}.png", dpi=300, bbox_inches="tight")
+        plt.close()
+
+# ---------------------------------------------------------------------
+# Additional Plot 10: Per-stream fault rates of shared and partitioned multicore caches.
+# ---------------------------------------------------------------------
+multicore_path = Path(__file__).parent / "multicore_results.csv"
+if multicore_path.exists():
+    df_mc = pd.read_csv(multicore_path, delimiter=";")
+    df_mc = df_mc.groupby(["k", "partitioning", "cache_strategy", "distribution"], as_index=False).agg({"fault_rate": "mean"})
+    for k in sorted(df_mc["k"].unique()):
+        plt.figure(figsize=(10, 6))
+        sns.barplot(
+            data=df_mc[(df_mc["k"] == k) & (df_mc["cache_strategy"] == "LRU")],
+            x="partitioning",
+            y="fault_rate",
+            hue="distribution",
+            palette="tab10"
+        )
+        plt.title(f"Per-Stream Fault Rate with LRU, Total Cache Size k = {k}")
+        plt.xlabel("Partitioning")
+        plt.ylabel("Fault Rate")
+        plt.legend(title="Stream Distribution")
+        plt.savefig(plots_dir / f"multicore_k{k}.png", dpi=300, bbox_inches="tight")
+        plt.close()
+
+# Additional Plot 11: Competitive ratio of LRU with lookahead against the window size.
+# ---------------------------------------------------------------------
+lookahead_path = Path(__file__).parent / "lookahead_results.csv"
+if lookahead_path.exists():
+    df_la = pd.read_csv(lookahead_path, delimiter=";")
+    df_la = df_la.groupby(["k", "model", "lookahead", "prefetch", "distribution"], as_index=False).agg({"ratio": "mean"})
+    df_la["setup"] = df_la["model"] + ", prefetch " + df_la["prefetch"].astype(str)
+    for k in sorted(df_la["k"].unique()):
+        for distribution in df_la["distribution"].unique():
+            plt.figure(figsize=(10, 6))
+            sns.lineplot(
+                data=df_la[(df_la["k"] == k) & (df_la["distribution"] == distribution)],
+                x="lookahead",
+                y="ratio",
+                hue="setup",
+                marker="o",
+                palette="tab10"
+            )
+            plt.xscale("symlog", base=2)
+            plt.axhline(1.0, color="gray", linestyle="--", label="MIN")
+            plt.title(f"LRU with Lookahead vs MIN, {distribution}, k = {k}")
+            plt.xlabel("Lookahead Window")
+            plt.ylabel("Cost / MIN Cost")
+            plt.legend(title="Model")
+            plt.savefig(plots_dir / f"lookahead_k{k}_{distribution}.png", dpi=300, bbox_inches="tight")
+            plt.close()
+
+# Additional Plot 12: Competitive ratio of the learning-augmented strategies against the
+# prediction error.
+# ---------------------------------------------------------------------
+prediction_path = Path(__file__).parent / "prediction_results.csv"
+if prediction_path.exists():
+    df_pred = pd.read_csv(prediction_path, delimiter=";")
+    df_pred = df_pred.groupby(["k", "sigma", "cache_strategy", "distribution"], as_index=False).agg({"ratio": "mean", "error_per_opt": "mean"})
+    for k in sorted(df_pred["k"].unique()):
+        for distribution in df_pred["distribution"].unique():
+            plt.figure(figsize=(10, 6))
+            sns.lineplot(
+                data=df_pred[(df_pred["k"] == k) & (df_pred["distribution"] == distribution)],
+                x="error_per_opt",
+                y="ratio",
+                hue="cache_strategy",
+                marker="o",
+                palette="tab10"
+            )
+            plt.xscale("symlog")
+            plt.title(f"Learning-Augmented Paging, {distribution}, k = {k}")
+            plt.xlabel("Prediction Error η / OPT")
+            plt.ylabel("Cost / MIN Cost")
+            plt.legend(title="Cache Strategy")
+            plt.savefig(plots_dir / f"predictions_k{k}_{distribution}.png", dpi=300, bbox_inches="tight")
+            plt.close()
+
 print("All plots generated and saved in the 'plots' directory.")
@@ -0,0 +1,188 @@
+use crate::cache::CacheManager;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Enum representing request sequence generators built to reproduce the lower bounds on the
+/// competitive ratio of paging strategies with a cache of `k` pages.
+///
+/// Every adversary requests pages from `1..=k + 1`, on which MIN misses at most once every
+/// `k` requests.
+#[derive(Debug)]
+pub enum Adversary {
+    /// Adaptive adversary: Inspects the cache after each access and requests the smallest
+    /// page that is not cached, so every request misses. Forces a ratio of `k` on every
+    /// deterministic strategy, and on randomized ones when it sees their random choices.
+    Adaptive {
+        /// The number of pages, `k + 1`.
+        pages: usize,
+    },
+    /// Oblivious adversary: Requests a page chosen uniformly at random among all but the
+    /// previous request. By Yao's principle no strategy pays less than H(k) times MIN on it.
+    Uniform {
+        /// The number of pages, `k + 1`.
+        pages: usize,
+        /// The previous request, if any.
+        previous: Option<usize>,
+        /// The random number generator.
+        rng: StdRng,
+    },
+    /// Oblivious adversary: Requests phases of `k` distinct pages. A phase starts with the page
+    /// left out of the previous phase, and each further page is chosen uniformly at random
+    /// among those not yet requested in the phase and followed by requests to every page of
+    /// the phase so far, so evicting them does not pay off. Each phase costs MIN one miss and
+    /// any strategy H(k) misses in expectation.
+    Phases {
+        /// The number of pages, `k + 1`.
+        pages: usize,
+        /// The page left out of the current phase.
+        left_out: usize,
+        /// The rest of the current phase, next request last.
+        pending: Vec<usize>,
+        /// The random number generator.
+        rng: StdRng,
+    },
+}
+
+impl Adversary {
+    /// Creates an adaptive adversary for a cache of `k` pages.
+    pub fn adaptive(k: usize) -> Self {
+        Adversary::Adaptive { pages: k + 1 }
+    }
+
+    /// Creates a uniform oblivious adversary for a cache of `k` pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The capacity of the cache under attack.
+    /// * `seed` - The seed of the adversary's random choices.
+    pub fn uniform(k: usize, seed: u64) -> Self {
+        Adversary::Uniform {
+            pages: k + 1,
+            previous: None,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Creates a phase-based oblivious adversary for a cache of `k` pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The capacity of the cache under attack.
+    /// * `seed` - The seed of the adversary's random choices.
+    pub fn phases(k: usize, seed: u64) -> Self {
+        Adversary::Phases {
+            pages: k + 1,
+            left_out: k + 1,
+            pending: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the next request, given the cache that will serve it.
+    ///
+    /// Only the adaptive adversary looks at the cache.
+    pub fn next_request(&mut self, cache: &CacheManager) -> usize {
+        match self {
+            Adversary::Adaptive { pages } => (1..=*pages)
+                .find(|&page_id| !cache.contains(page_id))
+                .expect("a cache of k pages cannot hold k + 1 pages"),
+            Adversary::Uniform {
+                pages,
+                previous,
+                rng,
+            } => {
+                // Draw from the pages but the previous one by skipping over it.
+                let mut page_id = rng.random_range(1..=*pages - usize::from(previous.is_some()));
+                if previous.is_some_and(|previous| page_id >= previous) {
+                    page_id += 1;
+                }
+                *previous = Some(page_id);
+                page_id
+            }
+            Adversary::Phases {
+                pages,
+                left_out,
+                pending,
+                rng,
+            } => {
+                if pending.is_empty() {
+                    // The page left out of the last phase opens the next one.
+                    let mut order: Vec<usize> = (1..=*pages).filter(|&p| p != *left_out).collect();
+                    order.shuffle(rng);
+                    order.insert(0, *left_out);
+                    *left_out = order.pop().unwrap();
+                    for (i, &page_id) in order.iter().enumerate() {
+                        pending.push(page_id);
+                        if i > 0 {
+                            pending.extend_from_slice(&order[..i]);
+                        }
+                    }
+                    pending.reverse();
+                }
+                pending.pop().unwrap()
+            }
+        }
+    }
+
+    /// Plays the adversary against a cache for `len` requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache under attack.
+    /// * `len` - The number of requests.
+    ///
+    /// # Returns
+    ///
+    /// The requests and the total cost the cache paid for them.
+    pub fn play(&mut self, cache: &mut CacheManager, len: usize) -> (Vec<usize>, usize) {
+        let mut requests = Vec::with_capacity(len);
+        let mut total_cost = 0;
+        for _ in 0..len {
+            let page_id = self.next_request(cache);
+            total_cost += cache.access(page_id);
+            requests.push(page_id);
+        }
+        (requests, total_cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheManagementStrategy, NextUse};
+
+    #[test]
+    fn test_adaptive_adversary() {
+        let mut cache = CacheManager::new(4, CacheManagementStrategy::LRU);
+        let (requests, cost) = Adversary::adaptive(4).play(&mut cache, 100);
+        assert_eq!(cost, 100);
+        // LRU is pushed into cycling through the 5 pages.
+        assert_eq!(requests[..6], [1, 2, 3, 4, 5, 1]);
+
+        let mut opt = CacheManager::new(4, CacheManagementStrategy::MIN(NextUse::new(&requests)));
+        let opt_cost: usize = requests.iter().map(|&page| opt.access(page)).sum();
+        assert!(opt_cost <= 4 + 100 / 4);
+    }
+
+    #[test]
+    fn test_oblivious_adversaries() {
+        let cache = CacheManager::new(3, CacheManagementStrategy::LRU);
+
+        let mut uniform = Adversary::uniform(3, 0);
+        let requests: Vec<usize> = (0..1000).map(|_| uniform.next_request(&cache)).collect();
+        assert!(requests.iter().all(|page| (1..=4).contains(page)));
+        assert!(requests.windows(2).all(|pair| pair[0] != pair[1]));
+
+        // Each phase of 1 + 2 + 3 requests holds 3 distinct pages, a, b, a, c, a, b, and opens
+        // with the page the previous one left out.
+        let mut phases = Adversary::phases(3, 0);
+        let requests: Vec<usize> = (0..600).map(|_| phases.next_request(&cache)).collect();
+        for (previous, phase) in requests.chunks(6).zip(requests.chunks(6).skip(1)) {
+            let (a, b, c) = (phase[0], phase[1], phase[3]);
+            assert_eq!(phase, [a, b, a, c, a, b]);
+            assert!(a != b && b != c && a != c);
+            assert!(!previous.contains(&a));
+        }
+    }
+}
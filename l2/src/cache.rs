@@ -1,23 +1,533 @@
-use rand::rngs::ThreadRng;
-use rand::seq::IteratorRandom;
-use rand::{Rng, seq::IndexedRandom};
-use std::collections::{HashMap, VecDeque};
+use crate::marking::{MarkingPhases, PhaseStats};
+use crate::page_list::PageList;
+use crate::prediction::{Predictions, next_arrivals};
+use online_core::OnlineAlgorithm;
+use online_core::seed::splitmix64;
+use rand::Rng;
+use rand::rngs::StdRng;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 /// Enum representing different cache management strategies.
+#[allow(clippy::upper_case_acronyms, clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum CacheManagementStrategy {
     /// First-In-First-Out strategy: Evicts the oldest page when full.
     FIFO,
     /// Flush-When-Full strategy: Clears the entire cache when full and a miss occurs.
     FWF,
-    /// Least-Recently-Used strategy: Tracks usage order to evict the least recently used page.
-    LRU(VecDeque<usize>),
+    /// Least-Recently-Used strategy: Keeps the cache in usage order to evict the least recently used page.
+    LRU,
     /// Least-Frequently-Used strategy: Tracks access frequencies to evict the least frequently used page.
-    LFU(HashMap<usize, usize>),
+    LFU(Frequencies),
     /// Random strategy: Evicts a random page when full.
-    RAND(ThreadRng),
-    /// RANDOMIZED MARKUP ALGORITHM: Evicts a page based on a randomized algorithm.
-    RMA(HashMap<usize, bool>, ThreadRng),
+    RAND(StdRng),
+    /// Marking algorithm: Marks pages as they are requested and evicts unmarked pages, starting
+    /// a new phase with every page unmarked once all cached pages are marked. The eviction
+    /// policy picks the victim (RMA evicts a random unmarked page).
+    Marking(MarkingPhases),
+    /// Belady's offline optimum: Evicts the page whose next request is furthest in the future.
+    MIN(NextUse),
+    /// GreedyDual strategy for weighted paging: Gives each page a credit equal to its fetch cost,
+    /// evicts the page with the least credit and charges that credit to every other cached page.
+    GreedyDual(Credits),
+    /// Weighted RMA: Like random marking, but evicts an unmarked page with probability inversely
+    /// proportional to its fetch cost. Tracks the unmarked pages and the costs of cached pages.
+    WRMA(PageList, HashMap<usize, f64>, StdRng),
+    /// Size-aware LRU: Evicts least recently used objects until the requested object fits.
+    SizeLRU,
+    /// GreedyDual-Size-Frequency: Like GreedyDual, but an object's credit is its access count
+    /// since it was cached times its fetch cost per byte. Tracks the access counts.
+    GDSF(Credits, HashMap<usize, usize>),
+    /// Landlord: Charges rent per byte to every cached object, evicting those whose credit runs
+    /// out. On a hit, the object's credit is topped up by the given fraction of the way back to
+    /// its fetch cost (1.0 gives GreedyDual-Size).
+    Landlord(Credits, f64),
+    /// CLOCK (second chance): Evicts the oldest page, but gives pages referenced since they
+    /// were loaded or last passed over a second chance. Tracks the referenced pages.
+    CLOCK(HashSet<usize>),
+    /// 2Q: Admits new pages to a small FIFO queue and promotes them to an LRU queue only when
+    /// they are requested again soon after leaving it, which makes it resistant to scans.
+    TwoQ(TwoQueues),
+    /// Adaptive Replacement Cache: Balances a recency list and a frequency list, adapting the
+    /// target size of each from hits in the ghost lists of their recently evicted pages.
+    ARC(AdaptiveLists),
+    /// Low Inter-reference Recency Set: Keeps the pages with the shortest reuse distance
+    /// resident and evicts from a small pool of pages with long or unknown reuse distance.
+    LIRS(LirsStack),
+    /// LRU with lookahead: Sees a window of upcoming requests and evicts the least recently
+    /// used page outside it, or the page whose next request in the window is the latest if
+    /// every cached page is in it. With prefetching, a miss also loads upcoming pages ahead of
+    /// demand.
+    Lookahead(Lookahead),
+    /// Follow the Prediction: Evicts the page predicted to be requested furthest in the
+    /// future, which makes it MIN under perfect predictions and arbitrarily bad under poor ones.
+    FollowPrediction(Predictions),
+    /// Robust combiner: Simulates two strategies on the same requests and follows the one
+    /// with fewer misses so far, switching only once the followed one has more than twice the
+    /// misses of the other. On a miss it evicts a page the followed strategy does not hold.
+    Combiner(Box<Combination>),
+}
+
+impl CacheManagementStrategy {
+    /// Returns the name of the strategy, as used in the results.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CacheManagementStrategy::FIFO => "FIFO",
+            CacheManagementStrategy::FWF => "FWF",
+            CacheManagementStrategy::LRU => "LRU",
+            CacheManagementStrategy::LFU(_) => "LFU",
+            CacheManagementStrategy::RAND(_) => "RAND",
+            CacheManagementStrategy::Marking(phases) => phases.eviction().name(),
+            CacheManagementStrategy::MIN(_) => "MIN",
+            CacheManagementStrategy::GreedyDual(_) => "GreedyDual",
+            CacheManagementStrategy::WRMA(_, _, _) => "WRMA",
+            CacheManagementStrategy::SizeLRU => "SizeLRU",
+            CacheManagementStrategy::GDSF(_, _) => "GDSF",
+            CacheManagementStrategy::Landlord(_, refresh) if *refresh == 1.0 => "GDS",
+            CacheManagementStrategy::Landlord(_, _) => "Landlord",
+            CacheManagementStrategy::CLOCK(_) => "CLOCK",
+            CacheManagementStrategy::TwoQ(_) => "2Q",
+            CacheManagementStrategy::ARC(_) => "ARC",
+            CacheManagementStrategy::LIRS(_) => "LIRS",
+            CacheManagementStrategy::Lookahead(_) => "LRU-LOOKAHEAD",
+            CacheManagementStrategy::FollowPrediction(_) => "FTP",
+            CacheManagementStrategy::Combiner(_) => "COMBINER",
+        }
+    }
+}
+
+/// Access counts of the cached pages used by the LFU strategy.
+///
+/// The cached pages are ordered by access count, then by the time they were loaded, so each
+/// access takes O(log k). Ties are broken by evicting the page that was loaded first.
+#[derive(Debug, Default)]
+pub struct Frequencies {
+    /// The cached pages ordered by access count, then by load stamp.
+    queue: BTreeSet<(usize, u64, usize)>,
+    /// The key of each cached page in `queue`.
+    keys: HashMap<usize, (usize, u64)>,
+    /// The number of pages loaded so far, used as the next load stamp.
+    loads: u64,
+}
+
+impl Frequencies {
+    /// Creates new, empty `Frequencies`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a newly loaded page with an access count of 1.
+    fn _load(&mut self, page_id: usize) {
+        self.loads += 1;
+        self.keys.insert(page_id, (1, self.loads));
+        self.queue.insert((1, self.loads, page_id));
+    }
+
+    /// Counts an access to a cached page.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the page was cached.
+    fn _hit(&mut self, page_id: usize) -> bool {
+        let Some((count, stamp)) = self.keys.get_mut(&page_id) else {
+            return false;
+        };
+        self.queue.remove(&(*count, *stamp, page_id));
+        *count += 1;
+        self.queue.insert((*count, *stamp, page_id));
+        true
+    }
+
+    /// Removes and returns the least frequently used page, the earliest loaded among ties.
+    fn _evict(&mut self) -> Option<usize> {
+        let (_, _, page_id) = self.queue.pop_first()?;
+        self.keys.remove(&page_id);
+        Some(page_id)
+    }
+
+    /// Forgets the access count of a page.
+    fn _remove(&mut self, page_id: usize) {
+        if let Some((count, stamp)) = self.keys.remove(&page_id) {
+            self.queue.remove(&(count, stamp, page_id));
+        }
+    }
+}
+
+/// An `f64` ordered with `total_cmp`, so it can be used as a key of ordered collections.
+#[derive(Debug, Clone, Copy)]
+struct Priority(f64);
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Priority {}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Credits of the cached pages used by the GreedyDual strategy.
+///
+/// Instead of lowering every credit on an eviction, the evicted credit is added to a global
+/// inflation value that new credits are offset by, so each access takes O(log k).
+#[derive(Debug, Default)]
+pub struct Credits {
+    /// The cached pages ordered by offset credit, then by the time of their last request.
+    queue: BTreeSet<(Priority, u64, usize)>,
+    /// The key of each cached page in `queue`.
+    keys: HashMap<usize, (Priority, u64)>,
+    /// The total credit charged to the cached pages by evictions so far.
+    inflation: f64,
+    /// The number of requests served, used to evict the least recently used page among ties.
+    time: u64,
+}
+
+impl Credits {
+    /// Creates new, empty `Credits`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the credit per byte of a cached page, refreshing its recency.
+    fn _set(&mut self, page_id: usize, credit_per_byte: f64) {
+        self.time += 1;
+        let key = (Priority(self.inflation + credit_per_byte), self.time);
+        if let Some((priority, time)) = self.keys.insert(page_id, key) {
+            self.queue.remove(&(priority, time, page_id));
+        }
+        self.queue.insert((key.0, key.1, page_id));
+    }
+
+    /// Forgets the credit of a page.
+    fn _remove(&mut self, page_id: usize) {
+        if let Some((priority, time)) = self.keys.remove(&page_id) {
+            self.queue.remove(&(priority, time, page_id));
+        }
+    }
+
+    /// Returns the remaining credit per byte of a cached page.
+    fn _credit_per_byte(&self, page_id: usize) -> Option<f64> {
+        self.keys
+            .get(&page_id)
+            .map(|(Priority(key), _)| key - self.inflation)
+    }
+
+    /// Evicts the pages with the least credit per byte until at most `limit` bytes are used,
+    /// charging each evicted credit to the remaining pages.
+    fn _evict_until(
+        &mut self,
+        memory: &mut PageList,
+        sizes: &mut HashMap<usize, usize>,
+        used: &mut usize,
+        limit: usize,
+    ) {
+        while *used > limit
+            && let Some((Priority(key), _, victim)) = self.queue.pop_first()
+        {
+            self.inflation = key;
+            self.keys.remove(&victim);
+            memory.remove(victim);
+            *used -= sizes.remove(&victim).unwrap();
+        }
+    }
+}
+
+/// Offline knowledge of a request sequence used by the MIN strategy.
+///
+/// MIN must be fed exactly the sequence it was built from, one request per access.
+#[derive(Debug)]
+pub struct NextUse {
+    /// `next_use[t]` is the index of the next request to the page requested at `t`,
+    /// or `usize::MAX` if it is never requested again.
+    next_use: Vec<usize>,
+    /// The index of the next request to be served.
+    time: usize,
+    /// The cached pages ordered by their next request, as (next request index, page).
+    queue: BTreeSet<(usize, usize)>,
+    /// The next request index of each cached page, i.e. its key in `queue`.
+    cached: HashMap<usize, usize>,
+}
+
+impl NextUse {
+    /// Creates the next-use table for the given request sequence in O(n).
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - The full sequence of page requests that will be served.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `NextUse`.
+    pub fn new(requests: &[usize]) -> Self {
+        NextUse {
+            next_use: next_arrivals(requests),
+            time: 0,
+            queue: BTreeSet::new(),
+            cached: HashMap::new(),
+        }
+    }
+}
+
+/// Enum representing how much of the upcoming requests a lookahead strategy sees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookaheadModel {
+    /// Weak lookahead: the next `l` requests.
+    Weak,
+    /// Strong lookahead, as defined by Albers: the next requests up to the last one before
+    /// the `l + 1`-th distinct page other than the current one, so the window always shows
+    /// `l` distinct pages however often they repeat.
+    Strong,
+}
+
+/// Limited knowledge of the upcoming requests used by the lookahead strategy.
+///
+/// Like MIN, the strategy must be fed exactly the sequence it was built from, one request
+/// per access.
+#[derive(Debug)]
+pub struct Lookahead {
+    /// The full sequence of page requests that will be served.
+    requests: Vec<usize>,
+    /// The index of the next request to be served.
+    time: usize,
+    /// The size `l` of the lookahead window.
+    window: usize,
+    /// How the window is measured.
+    model: LookaheadModel,
+    /// The greatest number of pages a miss may prefetch.
+    depth: usize,
+    /// The number of pages prefetched so far.
+    prefetches: usize,
+}
+
+impl Lookahead {
+    /// Creates the lookahead knowledge for the given request sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - The full sequence of page requests that will be served.
+    /// * `window` - The size `l` of the lookahead window. A window of 0 makes the strategy
+    ///   plain LRU, and a window as long as the sequence makes it as good as MIN.
+    /// * `model` - How the window is measured.
+    /// * `depth` - The greatest number of uncached pages of the window a miss also fetches,
+    ///   soonest first, or 0 to fetch on demand only.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Lookahead`.
+    pub fn new(requests: &[usize], window: usize, model: LookaheadModel, depth: usize) -> Self {
+        Lookahead {
+            requests: requests.to_vec(),
+            time: 0,
+            window,
+            model,
+            depth,
+            prefetches: 0,
+        }
+    }
+
+    /// Returns the first index of every page requested in the window after the request at
+    /// `time`.
+    fn _window(&self, time: usize) -> HashMap<usize, usize> {
+        let current = self.requests[time];
+        let mut seen = HashMap::new();
+        let mut distinct = 0;
+        for (t, &page_id) in self.requests.iter().enumerate().skip(time + 1) {
+            let fresh = !seen.contains_key(&page_id);
+            let in_window = match self.model {
+                LookaheadModel::Weak => t - time <= self.window,
+                LookaheadModel::Strong => !fresh || page_id == current || distinct < self.window,
+            };
+            if !in_window {
+                break;
+            }
+            if fresh {
+                seen.insert(page_id, t);
+                distinct += usize::from(page_id != current);
+            }
+        }
+        seen
+    }
+}
+
+/// The two strategies a combiner simulates, and which one it follows.
+#[derive(Debug)]
+pub struct Combination {
+    /// The simulated caches.
+    caches: [CacheManager; 2],
+    /// The number of misses of each simulated cache.
+    costs: [usize; 2],
+    /// The index of the followed cache.
+    following: usize,
+}
+
+impl Combination {
+    /// Creates a combination of two strategies, following the first one at the start.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - A cache running the first strategy.
+    /// * `second` - A cache running the second strategy.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Combination`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caches have different capacities.
+    pub fn new(first: CacheManager, second: CacheManager) -> Self {
+        assert_eq!(
+            first.capacity, second.capacity,
+            "Combined caches must have the same capacity"
+        );
+        Combination {
+            caches: [first, second],
+            costs: [0, 0],
+            following: 0,
+        }
+    }
+}
+
+/// Queues of the 2Q strategy.
+///
+/// The queue sizes follow the recommended tuning: `A1in` holds a quarter of the cache and
+/// `A1out` remembers as many evicted pages as half of the cache holds.
+#[derive(Debug, Default)]
+pub struct TwoQueues {
+    /// Resident pages requested once recently, in FIFO order.
+    a1_in: PageList,
+    /// Ghost entries of pages recently evicted from `a1_in`, in FIFO order.
+    a1_out: PageList,
+    /// Resident pages requested again after leaving `a1_in`, in LRU order.
+    am: PageList,
+}
+
+impl TwoQueues {
+    /// Creates new, empty `TwoQueues`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Lists of the ARC strategy.
+#[derive(Debug, Default)]
+pub struct AdaptiveLists {
+    /// Resident pages requested once recently, in LRU order.
+    t1: PageList,
+    /// Resident pages requested at least twice recently, in LRU order.
+    t2: PageList,
+    /// Ghost entries of pages recently evicted from `t1`, in LRU order.
+    b1: PageList,
+    /// Ghost entries of pages recently evicted from `t2`, in LRU order.
+    b2: PageList,
+    /// The target size of `t1`.
+    target: usize,
+}
+
+impl AdaptiveLists {
+    /// Creates new, empty `AdaptiveLists`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The status of a page tracked by the LIRS strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LirsStatus {
+    /// A resident page with low inter-reference recency.
+    Lir,
+    /// A resident page with high inter-reference recency.
+    ResidentHir,
+    /// An evicted page whose recency is still tracked in the stack.
+    NonResidentHir,
+}
+
+/// Stack and queue of the LIRS strategy.
+///
+/// One percent of the cache, and at least one page, is reserved for HIR pages.
+#[derive(Debug, Default)]
+pub struct LirsStack {
+    /// The recency stack, bottom first: every LIR page and the recent HIR pages.
+    stack: PageList,
+    /// The resident HIR pages, next victim first.
+    queue: PageList,
+    /// The status of every page in the cache or in the stack.
+    status: HashMap<usize, LirsStatus>,
+    /// The number of LIR pages.
+    lir_count: usize,
+}
+
+impl LirsStack {
+    /// Creates a new, empty `LirsStack`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves a page to the top of the stack, pushing it if it is not in the stack.
+    fn _push_top(&mut self, page_id: usize) {
+        if !self.stack.move_to_back(page_id) {
+            self.stack.push_back(page_id);
+        }
+    }
+
+    /// Removes HIR pages from the bottom of the stack until a LIR page is at the bottom.
+    fn _prune(&mut self) {
+        while let Some(bottom) = self.stack.front() {
+            match self.status[&bottom] {
+                LirsStatus::Lir => break,
+                LirsStatus::ResidentHir => {}
+                LirsStatus::NonResidentHir => {
+                    self.status.remove(&bottom);
+                }
+            }
+            self.stack.pop_front();
+        }
+    }
+
+    /// Turns the LIR page at the bottom of the stack into a resident HIR page.
+    fn _demote_bottom(&mut self) {
+        if let Some(bottom) = self.stack.pop_front() {
+            self.status.insert(bottom, LirsStatus::ResidentHir);
+            self.queue.push_back(bottom);
+            self.lir_count -= 1;
+            self._prune();
+        }
+    }
+}
+
+/// An event describing one access served by an observed `CacheManager`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessEvent {
+    /// The index of the access, counting from 0.
+    pub time: usize,
+    /// The ID of the requested page.
+    pub page_id: usize,
+    /// Whether the page was served from the cache.
+    pub hit: bool,
+    /// The pages evicted to serve the access.
+    pub evicted: Vec<usize>,
+    /// The hash of the cache contents after the access, as returned by `CacheManager::snapshot`.
+    pub snapshot: u64,
+}
+
+/// A callback receiving the events of an observed `CacheManager`.
+struct Observer(Box<dyn FnMut(&AccessEvent) + Send>);
+
+impl std::fmt::Debug for Observer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Observer")
+    }
 }
 
 /// Struct representing a cache manager that handles page requests based on a given strategy.
@@ -28,7 +538,15 @@ pub struct CacheManager {
     /// The maximum capacity of the cache.
     capacity: usize,
     /// The current memory (pages) stored in the cache.
-    memory: VecDeque<usize>,
+    memory: PageList,
+    /// The size of each cached object, maintained by the size-aware strategies only.
+    sizes: HashMap<usize, usize>,
+    /// The total size of the cached objects, maintained by the size-aware strategies only.
+    used: usize,
+    /// The number of accesses served so far.
+    time: usize,
+    /// The callback receiving an event for every access, if any.
+    observer: Option<Observer>,
 }
 
 impl CacheManager {
@@ -36,22 +554,73 @@ impl CacheManager {
     ///
     /// # Arguments
     ///
-    /// * `capacity` - The maximum number of pages the cache can hold.
+    /// * `capacity` - The maximum number of pages the cache can hold, or the maximum total
+    ///   size of the cached objects for the size-aware strategies.
     /// * `strategy` - The cache management strategy to use.
     ///
     /// # Returns
     ///
     /// A new instance of `CacheManager`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the strategy is a combiner whose simulated caches are larger than the cache,
+    /// since it could then find no page to evict.
     pub fn new(capacity: usize, strategy: CacheManagementStrategy) -> Self {
+        if let CacheManagementStrategy::Combiner(combination) = &strategy {
+            assert!(
+                combination.caches[0].capacity <= capacity,
+                "Combined caches must not be larger than the cache"
+            );
+        }
         CacheManager {
             strategy,
             capacity,
-            memory: VecDeque::with_capacity(capacity),
+            memory: PageList::with_capacity(capacity),
+            sizes: HashMap::new(),
+            used: 0,
+            time: 0,
+            observer: None,
         }
     }
 
+    /// Installs a callback that receives an `AccessEvent` for every following access,
+    /// replacing any previous one.
+    ///
+    /// Observing an access takes an extra O(k) time to find the evicted pages and hash the
+    /// cache, so caches are unobserved by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The callback receiving the events.
+    pub fn set_observer<F: FnMut(&AccessEvent) + Send + 'static>(&mut self, observer: F) {
+        self.observer = Some(Observer(Box::new(observer)));
+    }
+
+    /// Returns `true` if the page is cached.
+    pub fn contains(&self, page_id: usize) -> bool {
+        self.memory.contains(page_id)
+    }
+
+    /// Returns a hash of the set of cached pages.
+    ///
+    /// The hash does not depend on the order of the pages, so two caches holding the same
+    /// pages have the same hash, whichever strategy filled them.
+    pub fn snapshot(&self) -> u64 {
+        self.memory
+            .iter()
+            .fold(0, |hash, page_id| hash ^ splitmix64(page_id as u64))
+    }
+
     /// Accesses a page in the cache and returns the cost of the access.
     ///
+    /// Every strategy serves an access in O(1) expected time, except LFU, MIN and GreedyDual
+    /// which take O(log k), WRMA which takes O(k) per eviction, layered marking which takes
+    /// O(k) per request, FAR marking which takes time linear in the size of its access graph
+    /// per eviction, and Lookahead which takes O(k + w log w) per miss for a window of `w`
+    /// requests. Follow the Prediction takes O(k) per eviction, and the combiner the time of
+    /// both simulated strategies plus O(k) per miss.
+    ///
     /// # Arguments
     ///
     /// * `page_id` - The ID of the page being accessed.
@@ -60,13 +629,212 @@ impl CacheManager {
     ///
     /// The cost of the access (0 for a hit, 1 for a miss).
     pub fn access(&mut self, page_id: usize) -> usize {
+        self._access(page_id, 1, 1.0)
+    }
+
+    /// Accesses an object of the given size and fetch cost, for generalized (file) caching,
+    /// or for weighted paging with a size of 1.
+    ///
+    /// Size-aware strategies evict objects until the requested one fits and never cache an
+    /// object larger than the whole cache; the others ignore the size and count slots.
+    /// Cost-aware strategies use the weight to pick their victims; the others ignore it.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the object being accessed.
+    /// * `size` - The size of the object, in the unit of the capacity.
+    /// * `weight` - The cost of fetching the object on a miss.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access (0 for a hit, `weight` for a miss).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight is not positive and finite, as WRMA draws its victims with
+    /// probability inversely proportional to their weights.
+    pub fn access_sized(&mut self, page_id: usize, size: usize, weight: f64) -> f64 {
+        assert!(
+            weight > 0.0 && weight.is_finite(),
+            "Fetch costs must be positive and finite"
+        );
+        self._access(page_id, size, weight) as f64 * weight
+    }
+
+    /// Accesses a page like `access`, also returning the pages evicted to serve it.
+    ///
+    /// Takes an extra O(k) time on a miss to find the evicted pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access (0 for a hit, 1 for a miss) and the evicted pages.
+    pub fn access_with_evictions(&mut self, page_id: usize) -> (usize, Vec<usize>) {
+        let before: Vec<usize> = if self.memory.contains(page_id) {
+            Vec::new()
+        } else {
+            self.memory.iter().collect()
+        };
+        let cost = self._access(page_id, 1, 1.0);
+        let evicted = before
+            .into_iter()
+            .filter(|&pid| !self.memory.contains(pid))
+            .collect();
+        (cost, evicted)
+    }
+
+    /// Removes a page from the cache without serving a request, as when a coherence protocol
+    /// or an inclusive cache level below invalidates it.
+    ///
+    /// The strategy forgets the page as if it had never been cached, except that MIN,
+    /// Lookahead and the predictive strategies keep their knowledge of the request sequence,
+    /// LIRS keeps the page's recency while it is in the stack, and the combiner's simulated
+    /// caches keep the page. Ghost lists are left alone, as the page was not evicted.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page to remove.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the page was cached.
+    pub fn invalidate(&mut self, page_id: usize) -> bool {
+        if !self.memory.remove(page_id) {
+            return false;
+        }
+        if let Some(size) = self.sizes.remove(&page_id) {
+            self.used -= size;
+        }
+        match &mut self.strategy {
+            CacheManagementStrategy::FIFO
+            | CacheManagementStrategy::FWF
+            | CacheManagementStrategy::LRU
+            | CacheManagementStrategy::RAND(_)
+            | CacheManagementStrategy::SizeLRU
+            | CacheManagementStrategy::Lookahead(_)
+            | CacheManagementStrategy::FollowPrediction(_)
+            | CacheManagementStrategy::Combiner(_) => {}
+            CacheManagementStrategy::LFU(frequencies) => frequencies._remove(page_id),
+            CacheManagementStrategy::Marking(phases) => phases.invalidate(page_id),
+            CacheManagementStrategy::MIN(state) => {
+                let next = state.cached.remove(&page_id).unwrap();
+                state.queue.remove(&(next, page_id));
+            }
+            CacheManagementStrategy::GreedyDual(credits)
+            | CacheManagementStrategy::Landlord(credits, _) => credits._remove(page_id),
+            CacheManagementStrategy::WRMA(unmarked, weights, _) => {
+                unmarked.remove(page_id);
+                weights.remove(&page_id);
+            }
+            CacheManagementStrategy::GDSF(credits, frequency) => {
+                credits._remove(page_id);
+                frequency.remove(&page_id);
+            }
+            CacheManagementStrategy::CLOCK(referenced) => {
+                referenced.remove(&page_id);
+            }
+            CacheManagementStrategy::TwoQ(queues) => {
+                if !queues.a1_in.remove(page_id) {
+                    queues.am.remove(page_id);
+                }
+            }
+            CacheManagementStrategy::ARC(lists) => {
+                if !lists.t1.remove(page_id) {
+                    lists.t2.remove(page_id);
+                }
+            }
+            CacheManagementStrategy::LIRS(lirs) => {
+                if lirs.status[&page_id] == LirsStatus::Lir {
+                    lirs.stack.remove(page_id);
+                    lirs.status.remove(&page_id);
+                    lirs.lir_count -= 1;
+                    lirs._prune();
+                } else {
+                    lirs.queue.remove(page_id);
+                    if lirs.stack.contains(page_id) {
+                        lirs.status.insert(page_id, LirsStatus::NonResidentHir);
+                    } else {
+                        lirs.status.remove(&page_id);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the number of pages a lookahead strategy has prefetched, or `None` for other
+    /// strategies.
+    pub fn prefetches(&self) -> Option<usize> {
+        match &self.strategy {
+            CacheManagementStrategy::Lookahead(state) => Some(state.prefetches),
+            _ => None,
+        }
+    }
+
+    /// Returns the phase statistics of a marking strategy, or `None` for other strategies.
+    pub fn phases(&self) -> Option<&[PhaseStats]> {
+        match &self.strategy {
+            CacheManagementStrategy::Marking(phases) => Some(phases.phases()),
+            _ => None,
+        }
+    }
+
+    /// Serves an access, returning 1 on a miss and 0 on a hit, and reports it to the observer.
+    fn _access(&mut self, page_id: usize, size: usize, weight: f64) -> usize {
+        let time = self.time;
+        self.time += 1;
+        let Some(mut observer) = self.observer.take() else {
+            return self._dispatch(page_id, size, weight);
+        };
+        // Only a miss can evict, so only then remember what was cached before.
+        let before: Vec<usize> = if self.memory.contains(page_id) {
+            Vec::new()
+        } else {
+            self.memory.iter().collect()
+        };
+        let cost = self._dispatch(page_id, size, weight);
+        let evicted = before
+            .into_iter()
+            .filter(|&pid| !self.memory.contains(pid))
+            .collect();
+        (observer.0)(&AccessEvent {
+            time,
+            page_id,
+            hit: cost == 0,
+            evicted,
+            snapshot: self.snapshot(),
+        });
+        self.observer = Some(observer);
+        cost
+    }
+
+    /// Dispatches an access to the strategy, returning 1 on a miss and 0 on a hit.
+    fn _dispatch(&mut self, page_id: usize, size: usize, weight: f64) -> usize {
         match &mut self.strategy {
             CacheManagementStrategy::FIFO => self._fifo_access(page_id),
             CacheManagementStrategy::FWF => self._fwf_access(page_id),
-            CacheManagementStrategy::LRU(_) => self._lru_access(page_id),
+            CacheManagementStrategy::LRU => self._lru_access(page_id),
             CacheManagementStrategy::LFU(_) => self._lfu_access(page_id),
             CacheManagementStrategy::RAND(_) => self._random_access(page_id),
-            CacheManagementStrategy::RMA(_, _) => self._rma_access(page_id),
+            CacheManagementStrategy::Marking(phases) => {
+                phases.access(&mut self.memory, self.capacity, page_id)
+            }
+            CacheManagementStrategy::MIN(_) => self._min_access(page_id),
+            CacheManagementStrategy::GreedyDual(_) => self._greedy_dual_access(page_id, weight),
+            CacheManagementStrategy::WRMA(_, _, _) => self._wrma_access(page_id, weight),
+            CacheManagementStrategy::SizeLRU => self._size_lru_access(page_id, size),
+            CacheManagementStrategy::GDSF(_, _) => self._gdsf_access(page_id, size, weight),
+            CacheManagementStrategy::Landlord(_, _) => self._landlord_access(page_id, size, weight),
+            CacheManagementStrategy::CLOCK(_) => self._clock_access(page_id),
+            CacheManagementStrategy::TwoQ(_) => self._two_q_access(page_id),
+            CacheManagementStrategy::ARC(_) => self._arc_access(page_id),
+            CacheManagementStrategy::LIRS(_) => self._lirs_access(page_id),
+            CacheManagementStrategy::Lookahead(_) => self._lookahead_access(page_id),
+            CacheManagementStrategy::FollowPrediction(_) => self._follow_prediction_access(page_id),
+            CacheManagementStrategy::Combiner(_) => self._combiner_access(page_id),
         }
     }
 
@@ -80,7 +848,7 @@ impl CacheManager {
     ///
     /// The cost of the access.
     fn _fifo_access(&mut self, page_id: usize) -> usize {
-        if self.memory.contains(&page_id) {
+        if self.memory.contains(page_id) {
             0 // Hit: no cost
         } else {
             if self.memory.len() == self.capacity {
@@ -101,7 +869,7 @@ impl CacheManager {
     ///
     /// The cost of the access.
     fn _fwf_access(&mut self, page_id: usize) -> usize {
-        if self.memory.contains(&page_id) {
+        if self.memory.contains(page_id) {
             0 // Hit
         } else {
             if self.memory.len() == self.capacity {
@@ -114,6 +882,8 @@ impl CacheManager {
 
     /// Handles page access using the LRU strategy.
     ///
+    /// The memory is kept in usage order, least recently used page at the front.
+    ///
     /// # Arguments
     ///
     /// * `page_id` - The ID of the page being accessed.
@@ -122,29 +892,14 @@ impl CacheManager {
     ///
     /// The cost of the access.
     fn _lru_access(&mut self, page_id: usize) -> usize {
-        if let CacheManagementStrategy::LRU(usage_order) = &mut self.strategy {
-            if self.memory.contains(&page_id) {
-                // Move to most recently used
-                if let Some(pos) = usage_order.iter().position(|&x| x == page_id) {
-                    usage_order.remove(pos);
-                }
-                usage_order.push_back(page_id);
-                0
-            } else {
-                // Miss: possibly evict
-                if self.memory.len() == self.capacity {
-                    if let Some(lru) = usage_order.pop_front() {
-                        if let Some(pos) = self.memory.iter().position(|&x| x == lru) {
-                            self.memory.remove(pos);
-                        }
-                    }
-                }
-                self.memory.push_back(page_id);
-                usage_order.push_back(page_id);
-                1
-            }
+        if self.memory.move_to_back(page_id) {
+            0 // Hit: now the most recently used
         } else {
-            panic!("_lru_access called with non-LRU strategy");
+            if self.memory.len() == self.capacity {
+                self.memory.pop_front(); // Evict least recently used
+            }
+            self.memory.push_back(page_id);
+            1
         }
     }
 
@@ -158,31 +913,18 @@ impl CacheManager {
     ///
     /// The cost of the access.
     fn _lfu_access(&mut self, page_id: usize) -> usize {
-        if let CacheManagementStrategy::LFU(freq_map) = &mut self.strategy {
-            if self.memory.contains(&page_id) {
-                // Hit: increase frequency
-                *freq_map.entry(page_id).or_insert(0) += 1;
-                0
+        if let CacheManagementStrategy::LFU(frequencies) = &mut self.strategy {
+            if frequencies._hit(page_id) {
+                0 // Hit
             } else {
                 // Miss
-                if self.memory.len() == self.capacity {
-                    // Find LFU page
-                    if let Some((lfu_page, _)) = self
-                        .memory
-                        .iter()
-                        .min_by_key(|&&pid| freq_map.get(&pid).copied().unwrap_or(0))
-                        .map(|&pid| (pid, freq_map.get(&pid).copied().unwrap_or(0)))
-                    {
-                        // Remove LFU page
-                        if let Some(pos) = self.memory.iter().position(|&x| x == lfu_page) {
-                            self.memory.remove(pos);
-                        }
-                        freq_map.remove(&lfu_page);
-                    }
+                if self.memory.len() == self.capacity
+                    && let Some(lfu_page) = frequencies._evict()
+                {
+                    self.memory.remove(lfu_page);
                 }
-
                 self.memory.push_back(page_id);
-                freq_map.insert(page_id, 1);
+                frequencies._load(page_id);
                 1
             }
         } else {
@@ -206,14 +948,14 @@ impl CacheManager {
     ///
     /// This function will panic if the cache management strategy is not `RAND`.
     fn _random_access(&mut self, page_id: usize) -> usize {
-        if self.memory.contains(&page_id) {
+        if self.memory.contains(page_id) {
             0 // Hit
         } else {
             if self.memory.len() == self.capacity {
                 if let CacheManagementStrategy::RAND(rng) = &mut self.strategy {
                     // Evict a random page from the cache
-                    let index = rng.random_range(0..self.memory.len());
-                    self.memory.remove(index);
+                    let victim = self.memory.get(rng.random_range(0..self.memory.len()));
+                    self.memory.remove(victim);
                 } else {
                     panic!("_random_access called with non-RAND strategy");
                 }
@@ -224,66 +966,720 @@ impl CacheManager {
         }
     }
 
-    /// RMA strategy: Randomized Markup Algorithm.
-    /// On a hit, the page is marked.
-    /// On a miss:
-    ///   - If there's room, add the page and mark it.
-    ///   - If full, evict an unmarked page chosen uniformly at random.
-    ///   - If all pages are marked, clear marks and then evict one at random.
-    fn _rma_access(&mut self, page_id: usize) -> usize {
-        if let CacheManagementStrategy::RMA(mark_map, rng) = &mut self.strategy {
-            if self.memory.contains(&page_id) {
-                // Hit: mark the page.
-                mark_map.insert(page_id, true);
+    /// Handles page access using Belady's MIN strategy in O(log k).
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the requests deviate from the sequence the strategy was built from.
+    fn _min_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::MIN(state) = &mut self.strategy {
+            let time = state.time;
+            assert!(
+                time < state.next_use.len(),
+                "_min_access called past the end of the request sequence"
+            );
+            let next = state.next_use[time];
+            state.time += 1;
+            if let Some(current) = state.cached.insert(page_id, next) {
+                // Hit: the page is now keyed by its following request.
+                assert_eq!(
+                    current, time,
+                    "_min_access called with a different request sequence"
+                );
+                state.queue.remove(&(current, page_id));
+                state.queue.insert((next, page_id));
                 0
             } else {
-                if self.memory.len() < self.capacity {
-                    self.memory.push_back(page_id);
-                    mark_map.insert(page_id, true);
-                    1
-                } else {
-                    // Cache is full.
-                    let unmarked: Vec<usize> = self
-                        .memory
-                        .iter()
-                        .cloned()
-                        .filter(|pid| !*mark_map.get(pid).unwrap_or(&false))
-                        .collect();
-                    let victim = if !unmarked.is_empty() {
-                        unmarked.choose(rng).copied().unwrap()
-                    } else {
-                        // All pages are marked; clear marks.
-                        for pid in self.memory.iter() {
-                            mark_map.insert(*pid, false);
-                        }
-                        self.memory.iter().choose(rng).copied().unwrap()
-                    };
-                    if let Some(pos) = self.memory.iter().position(|&x| x == victim) {
-                        self.memory.remove(pos);
+                if self.memory.len() == self.capacity
+                    && let Some((_, victim)) = state.queue.pop_last()
+                {
+                    // Evict the page requested furthest in the future.
+                    state.cached.remove(&victim);
+                    self.memory.remove(victim);
+                }
+                state.queue.insert((next, page_id));
+                self.memory.push_back(page_id);
+                1
+            }
+        } else {
+            panic!("_min_access called with non-MIN strategy");
+        }
+    }
+
+    /// Handles page access using the GreedyDual strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    /// * `weight` - The cost of fetching the page on a miss.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _greedy_dual_access(&mut self, page_id: usize, weight: f64) -> usize {
+        if let CacheManagementStrategy::GreedyDual(credits) = &mut self.strategy {
+            credits.time += 1;
+            let key = (Priority(credits.inflation + weight), credits.time);
+            if let Some((priority, time)) = credits.keys.insert(page_id, key) {
+                // Hit: restore the page's full credit.
+                credits.queue.remove(&(priority, time, page_id));
+                credits.queue.insert((key.0, key.1, page_id));
+                0
+            } else {
+                if self.memory.len() == self.capacity
+                    && let Some((Priority(credit), _, victim)) = credits.queue.pop_first()
+                {
+                    // Evict the page with the least credit and charge it to the others.
+                    credits.inflation = credit;
+                    credits.keys.remove(&victim);
+                    self.memory.remove(victim);
+                }
+                let key = (Priority(credits.inflation + weight), credits.time);
+                credits.keys.insert(page_id, key);
+                credits.queue.insert((key.0, key.1, page_id));
+                self.memory.push_back(page_id);
+                1
+            }
+        } else {
+            panic!("_greedy_dual_access called with non-GreedyDual strategy");
+        }
+    }
+
+    /// Handles page access using the weighted RMA strategy.
+    ///
+    /// Works like random marking, except that the victim is drawn from the unmarked pages with
+    /// probability proportional to the inverse of their fetch cost.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    /// * `weight` - The cost of fetching the page on a miss.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _wrma_access(&mut self, page_id: usize, weight: f64) -> usize {
+        if let CacheManagementStrategy::WRMA(unmarked, weights, rng) = &mut self.strategy {
+            if self.memory.contains(page_id) {
+                // Hit: mark the page.
+                unmarked.remove(page_id);
+                0
+            } else {
+                if self.memory.len() == self.capacity {
+                    if unmarked.is_empty() {
+                        // All pages are marked; clear marks.
+                        for pid in self.memory.iter() {
+                            unmarked.push_back(pid);
+                        }
+                    }
+                    let total: f64 = unmarked.iter().map(|pid| 1.0 / weights[&pid]).sum();
+                    let mut target = rng.random_range(0.0..total);
+                    let mut victim = unmarked.front().unwrap();
+                    for pid in unmarked.iter() {
+                        victim = pid;
+                        target -= 1.0 / weights[&pid];
+                        if target < 0.0 {
+                            break;
+                        }
+                    }
+                    unmarked.remove(victim);
+                    weights.remove(&victim);
+                    self.memory.remove(victim);
+                }
+                // The new page is marked.
+                weights.insert(page_id, weight);
+                self.memory.push_back(page_id);
+                1
+            }
+        } else {
+            panic!("_wrma_access called with non-WRMA strategy");
+        }
+    }
+
+    /// Handles object access using the size-aware LRU strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the object being accessed.
+    /// * `size` - The size of the object.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _size_lru_access(&mut self, page_id: usize, size: usize) -> usize {
+        if self.memory.move_to_back(page_id) {
+            0 // Hit: now the most recently used
+        } else {
+            if size > self.capacity {
+                return 1; // Too large to cache at all
+            }
+            while self.used + size > self.capacity
+                && let Some(lru) = self.memory.pop_front()
+            {
+                self.used -= self.sizes.remove(&lru).unwrap();
+            }
+            self.memory.push_back(page_id);
+            self.sizes.insert(page_id, size);
+            self.used += size;
+            1
+        }
+    }
+
+    /// Handles object access using the GDSF strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the object being accessed.
+    /// * `size` - The size of the object.
+    /// * `weight` - The cost of fetching the object on a miss.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _gdsf_access(&mut self, page_id: usize, size: usize, weight: f64) -> usize {
+        if let CacheManagementStrategy::GDSF(credits, frequency) = &mut self.strategy {
+            if self.memory.contains(page_id) {
+                // Hit: the object earns credit for one more access.
+                let count = frequency.entry(page_id).or_insert(0);
+                *count += 1;
+                credits._set(
+                    page_id,
+                    *count as f64 * weight / self.sizes[&page_id] as f64,
+                );
+                0
+            } else {
+                if size > self.capacity {
+                    return 1; // Too large to cache at all
+                }
+                let limit = self.capacity - size;
+                credits._evict_until(&mut self.memory, &mut self.sizes, &mut self.used, limit);
+                // Counts of evicted objects are stale and restart here.
+                frequency.insert(page_id, 1);
+                credits._set(page_id, weight / size as f64);
+                self.memory.push_back(page_id);
+                self.sizes.insert(page_id, size);
+                self.used += size;
+                1
+            }
+        } else {
+            panic!("_gdsf_access called with non-GDSF strategy");
+        }
+    }
+
+    /// Handles object access using the Landlord strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the object being accessed.
+    /// * `size` - The size of the object.
+    /// * `weight` - The cost of fetching the object on a miss.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _landlord_access(&mut self, page_id: usize, size: usize, weight: f64) -> usize {
+        if let CacheManagementStrategy::Landlord(credits, refresh) = &mut self.strategy {
+            if let Some(credit_per_byte) = credits._credit_per_byte(page_id) {
+                // Hit: top the credit up towards the full fetch cost.
+                let size = self.sizes[&page_id] as f64;
+                let credit = credit_per_byte * size;
+                credits._set(page_id, (credit + *refresh * (weight - credit)) / size);
+                0
+            } else {
+                if size > self.capacity {
+                    return 1; // Too large to cache at all
+                }
+                let limit = self.capacity - size;
+                credits._evict_until(&mut self.memory, &mut self.sizes, &mut self.used, limit);
+                credits._set(page_id, weight / size as f64);
+                self.memory.push_back(page_id);
+                self.sizes.insert(page_id, size);
+                self.used += size;
+                1
+            }
+        } else {
+            panic!("_landlord_access called with non-Landlord strategy");
+        }
+    }
+
+    /// Handles page access using the CLOCK strategy.
+    ///
+    /// The memory is kept in the order the clock hand visits the pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _clock_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::CLOCK(referenced) = &mut self.strategy {
+            if self.memory.contains(page_id) {
+                // Hit: set the reference bit.
+                referenced.insert(page_id);
+                0
+            } else {
+                if self.memory.len() == self.capacity {
+                    // Advance the hand, clearing reference bits, to the first unreferenced page.
+                    while let Some(page) = self.memory.front() {
+                        if referenced.remove(&page) {
+                            self.memory.move_to_back(page);
+                        } else {
+                            self.memory.pop_front();
+                            break;
+                        }
+                    }
+                }
+                self.memory.push_back(page_id);
+                1
+            }
+        } else {
+            panic!("_clock_access called with non-CLOCK strategy");
+        }
+    }
+
+    /// Handles page access using the 2Q strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _two_q_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::TwoQ(queues) = &mut self.strategy {
+            // A hit in Am makes the page the most recently used; a hit in A1in is a correlated
+            // reference, which leaves the page where it is.
+            if queues.am.move_to_back(page_id) || queues.a1_in.contains(page_id) {
+                0
+            } else {
+                let promote = queues.a1_out.remove(page_id);
+                if self.memory.len() == self.capacity {
+                    // Reclaim a frame from A1in if it is over its share, otherwise from Am.
+                    let in_size = (self.capacity / 4).max(1);
+                    let out_size = (self.capacity / 2).max(1);
+                    let victim = if queues.a1_in.len() > in_size || queues.am.is_empty() {
+                        let victim = queues.a1_in.pop_front().unwrap();
+                        queues.a1_out.push_back(victim);
+                        if queues.a1_out.len() > out_size {
+                            queues.a1_out.pop_front();
+                        }
+                        victim
+                    } else {
+                        queues.am.pop_front().unwrap()
+                    };
+                    self.memory.remove(victim);
+                }
+                if promote {
+                    queues.am.push_back(page_id);
+                } else {
+                    queues.a1_in.push_back(page_id);
+                }
+                self.memory.push_back(page_id);
+                1
+            }
+        } else {
+            panic!("_two_q_access called with non-TwoQ strategy");
+        }
+    }
+
+    /// Handles page access using the ARC strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _arc_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::ARC(lists) = &mut self.strategy {
+            let capacity = self.capacity;
+            if lists.t1.remove(page_id) || lists.t2.remove(page_id) {
+                // Hit: the page has now been requested at least twice.
+                lists.t2.push_back(page_id);
+                return 0;
+            }
+
+            let in_b2 = lists.b2.contains(page_id);
+            if lists.b1.remove(page_id) {
+                // Ghost hit in B1: recency deserves more room.
+                let delta = (lists.b2.len() / (lists.b1.len() + 1)).max(1);
+                lists.target = (lists.target + delta).min(capacity);
+            } else if lists.b2.remove(page_id) {
+                // Ghost hit in B2: frequency deserves more room.
+                let delta = (lists.b1.len() / (lists.b2.len() + 1)).max(1);
+                lists.target = lists.target.saturating_sub(delta);
+            } else {
+                // A new page: keep the directory within twice the capacity.
+                if lists.t1.len() + lists.b1.len() == capacity {
+                    if lists.t1.len() < capacity {
+                        lists.b1.pop_front();
+                    } else {
+                        let victim = lists.t1.pop_front().unwrap();
+                        self.memory.remove(victim);
+                    }
+                } else if lists.t1.len() + lists.t2.len() + lists.b1.len() + lists.b2.len()
+                    >= 2 * capacity
+                {
+                    lists.b2.pop_front();
+                }
+                if self.memory.len() == capacity {
+                    Self::_arc_replace(lists, &mut self.memory, false);
+                }
+                lists.t1.push_back(page_id);
+                self.memory.push_back(page_id);
+                return 1;
+            }
+
+            if self.memory.len() == capacity {
+                Self::_arc_replace(lists, &mut self.memory, in_b2);
+            }
+            lists.t2.push_back(page_id);
+            self.memory.push_back(page_id);
+            1
+        } else {
+            panic!("_arc_access called with non-ARC strategy");
+        }
+    }
+
+    /// Evicts the least recently used page of `t1` or `t2` into its ghost list, steering the
+    /// size of `t1` towards its target.
+    ///
+    /// # Arguments
+    ///
+    /// * `lists` - The ARC lists.
+    /// * `memory` - The resident pages.
+    /// * `in_b2` - Whether the requested page was found in `b2`.
+    fn _arc_replace(lists: &mut AdaptiveLists, memory: &mut PageList, in_b2: bool) {
+        let t1_len = lists.t1.len();
+        if t1_len > 0 && (t1_len > lists.target || (in_b2 && t1_len == lists.target)) {
+            let victim = lists.t1.pop_front().unwrap();
+            lists.b1.push_back(victim);
+            memory.remove(victim);
+        } else if let Some(victim) = lists.t2.pop_front() {
+            lists.b2.push_back(victim);
+            memory.remove(victim);
+        }
+    }
+
+    /// Handles page access using the LIRS strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is below 2, as LIRS needs room for a LIR and a HIR page.
+    fn _lirs_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::LIRS(lirs) = &mut self.strategy {
+            assert!(self.capacity >= 2, "LIRS needs a capacity of at least 2");
+            let lir_capacity = self.capacity - (self.capacity / 100).max(1);
+            match lirs.status.get(&page_id).copied() {
+                Some(LirsStatus::Lir) => {
+                    // Hit on a LIR page: move it to the top, pruning if it was at the bottom.
+                    let was_bottom = lirs.stack.front() == Some(page_id);
+                    lirs.stack.move_to_back(page_id);
+                    if was_bottom {
+                        lirs._prune();
+                    }
+                    0
+                }
+                Some(LirsStatus::ResidentHir) => {
+                    if lirs.stack.move_to_back(page_id) {
+                        // Its reuse distance beats the oldest LIR page's, so the two swap roles.
+                        lirs.queue.remove(page_id);
+                        lirs.status.insert(page_id, LirsStatus::Lir);
+                        lirs.lir_count += 1;
+                        lirs._demote_bottom();
+                    } else {
+                        lirs.stack.push_back(page_id);
+                        lirs.queue.move_to_back(page_id);
+                    }
+                    0
+                }
+                status => {
+                    if self.memory.len() == self.capacity
+                        && let Some(victim) = lirs.queue.pop_front()
+                    {
+                        // Evict a resident HIR page, remembering it while it is in the stack.
+                        self.memory.remove(victim);
+                        if lirs.stack.contains(victim) {
+                            lirs.status.insert(victim, LirsStatus::NonResidentHir);
+                        } else {
+                            lirs.status.remove(&victim);
+                        }
+                    }
+                    if lirs.lir_count < lir_capacity {
+                        // Warm-up: LIR pages fill their share of the cache first.
+                        lirs._push_top(page_id);
+                        lirs.status.insert(page_id, LirsStatus::Lir);
+                        lirs.lir_count += 1;
+                    } else if status.is_some() && lirs.stack.contains(page_id) {
+                        // A non-resident page with a short reuse distance comes back as LIR.
+                        lirs.stack.move_to_back(page_id);
+                        lirs.status.insert(page_id, LirsStatus::Lir);
+                        lirs.lir_count += 1;
+                        lirs._demote_bottom();
+                    } else {
+                        lirs._push_top(page_id);
+                        lirs.queue.push_back(page_id);
+                        lirs.status.insert(page_id, LirsStatus::ResidentHir);
                     }
-                    mark_map.remove(&victim);
                     self.memory.push_back(page_id);
-                    mark_map.insert(page_id, true);
                     1
                 }
             }
         } else {
-            panic!("_rma_access called with non-RMA strategy");
+            panic!("_lirs_access called with non-LIRS strategy");
         }
     }
+
+    /// Handles page access using LRU with lookahead, prefetching upcoming pages on a miss.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access. Prefetches are counted separately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the requests deviate from the sequence the strategy was built from.
+    fn _lookahead_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::Lookahead(state) = &mut self.strategy {
+            let time = state.time;
+            assert!(
+                time < state.requests.len(),
+                "_lookahead_access called past the end of the request sequence"
+            );
+            assert_eq!(
+                state.requests[time], page_id,
+                "_lookahead_access called with a different request sequence"
+            );
+            state.time += 1;
+            if self.memory.move_to_back(page_id) {
+                return 0; // Hit: now the most recently used
+            }
+
+            // The requested page counts as requested now, so prefetching never evicts it.
+            let mut seen = state._window(time);
+            seen.insert(page_id, time);
+            if self.memory.len() == self.capacity
+                && let Some(victim) = Self::_lookahead_victim(&self.memory, &seen, None)
+            {
+                self.memory.remove(victim);
+            }
+            self.memory.push_back(page_id);
+
+            if state.depth > 0 {
+                let mut upcoming: Vec<(usize, usize)> = seen
+                    .iter()
+                    .filter(|&(&pid, _)| !self.memory.contains(pid))
+                    .map(|(&pid, &next)| (next, pid))
+                    .collect();
+                upcoming.sort_unstable();
+                for (next, pid) in upcoming.into_iter().take(state.depth) {
+                    // Only make room with a page that is not needed before the prefetched one.
+                    if self.memory.len() == self.capacity {
+                        match Self::_lookahead_victim(&self.memory, &seen, Some(next)) {
+                            Some(victim) => self.memory.remove(victim),
+                            None => break,
+                        };
+                    }
+                    self.memory.push_back(pid);
+                    state.prefetches += 1;
+                }
+            }
+            1
+        } else {
+            panic!("_lookahead_access called with non-Lookahead strategy");
+        }
+    }
+
+    /// Handles page access by following the predictions.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _follow_prediction_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::FollowPrediction(predictions) = &mut self.strategy {
+            let cost = if self.memory.contains(page_id) {
+                0
+            } else {
+                if self.memory.len() == self.capacity
+                    && let Some(victim) = predictions.furthest(self.memory.iter())
+                {
+                    self.memory.remove(victim);
+                }
+                self.memory.push_back(page_id);
+                1
+            };
+            predictions.observe(page_id);
+            cost
+        } else {
+            panic!("_follow_prediction_access called with non-FollowPrediction strategy");
+        }
+    }
+
+    /// Handles page access using the combiner.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access.
+    fn _combiner_access(&mut self, page_id: usize) -> usize {
+        if let CacheManagementStrategy::Combiner(combination) = &mut self.strategy {
+            for (cache, cost) in combination.caches.iter_mut().zip(&mut combination.costs) {
+                *cost += cache.access(page_id);
+            }
+            let other = 1 - combination.following;
+            if combination.costs[combination.following] > 2 * combination.costs[other] {
+                combination.following = other;
+            }
+            if self.memory.contains(page_id) {
+                0
+            } else {
+                if self.memory.len() == self.capacity {
+                    // The followed cache holds the requested page and at most `k - 1` others,
+                    // so one of ours is missing from it.
+                    let followed = &combination.caches[combination.following];
+                    let victim = self
+                        .memory
+                        .iter()
+                        .find(|&pid| !followed.contains(pid))
+                        .expect("The combined caches are never larger than the cache");
+                    self.memory.remove(victim);
+                }
+                self.memory.push_back(page_id);
+                1
+            }
+        } else {
+            panic!("_combiner_access called with non-Combiner strategy");
+        }
+    }
+
+    /// Picks the page the lookahead strategy evicts: the least recently used page outside
+    /// the window, or else the page whose next request in the window is the latest, provided
+    /// it comes after the request at index `after`, if given.
+    fn _lookahead_victim(
+        memory: &PageList,
+        seen: &HashMap<usize, usize>,
+        after: Option<usize>,
+    ) -> Option<usize> {
+        memory
+            .iter()
+            .find(|pid| !seen.contains_key(pid))
+            .or_else(|| {
+                memory
+                    .iter()
+                    .max_by_key(|pid| seen[pid])
+                    .filter(|pid| after.is_none_or(|after| seen[pid] > after))
+            })
+    }
+}
+
+/// Serves unit-size, unit-cost page requests.
+impl OnlineAlgorithm<usize> for CacheManager {
+    fn serve(&mut self, page_id: usize) -> u64 {
+        self.access(page_id) as u64
+    }
+
+    /// Empties the cache and restores the initial state of the strategy, keeping the
+    /// observer. Strategies built from a request sequence start over at its first request.
+    fn reset(&mut self) {
+        self.memory.clear();
+        self.sizes.clear();
+        self.used = 0;
+        self.time = 0;
+        match &mut self.strategy {
+            CacheManagementStrategy::FIFO
+            | CacheManagementStrategy::FWF
+            | CacheManagementStrategy::LRU
+            | CacheManagementStrategy::RAND(_)
+            | CacheManagementStrategy::SizeLRU => {}
+            CacheManagementStrategy::LFU(state) => *state = Frequencies::new(),
+            CacheManagementStrategy::Marking(phases) => phases.reset(),
+            CacheManagementStrategy::MIN(state) => {
+                state.time = 0;
+                state.queue.clear();
+                state.cached.clear();
+            }
+            CacheManagementStrategy::GreedyDual(credits)
+            | CacheManagementStrategy::Landlord(credits, _) => *credits = Credits::new(),
+            CacheManagementStrategy::WRMA(unmarked, weights, _) => {
+                unmarked.clear();
+                weights.clear();
+            }
+            CacheManagementStrategy::GDSF(credits, frequency) => {
+                *credits = Credits::new();
+                frequency.clear();
+            }
+            CacheManagementStrategy::CLOCK(referenced) => referenced.clear(),
+            CacheManagementStrategy::TwoQ(queues) => *queues = TwoQueues::new(),
+            CacheManagementStrategy::ARC(lists) => *lists = AdaptiveLists::new(),
+            CacheManagementStrategy::LIRS(lirs) => *lirs = LirsStack::new(),
+            CacheManagementStrategy::Lookahead(state) => {
+                state.time = 0;
+                state.prefetches = 0;
+            }
+            CacheManagementStrategy::FollowPrediction(predictions) => predictions.reset(),
+            CacheManagementStrategy::Combiner(combination) => {
+                for cache in &mut combination.caches {
+                    cache.reset();
+                }
+                combination.costs = [0, 0];
+                combination.following = 0;
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.strategy.name()
+    }
+
+    fn snapshot(&self) -> u64 {
+        CacheManager::snapshot(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::rng;
+    use crate::marking::{Chains, Layers, MarkingEviction};
+    use crate::prediction::Predictor;
+    use rand::SeedableRng;
 
     /// Tests the FIFO strategy for correctness.
     #[test]
     fn test_fifo_strategy() {
-        let requests = vec![1, 2, 3, 1, 4, 2, 5];
+        let requests = [1, 2, 3, 1, 4, 2, 5];
         let mut cache = CacheManager::new(3, CacheManagementStrategy::FIFO);
-        let expected_costs = vec![1, 1, 1, 0, 1, 0, 1];
+        let expected_costs = [1, 1, 1, 0, 1, 0, 1];
 
         for (i, &req) in requests.iter().enumerate() {
             let cost = cache.access(req);
@@ -294,9 +1690,9 @@ mod tests {
     /// Tests the FWF strategy for correctness.
     #[test]
     fn test_fwf_strategy() {
-        let requests = vec![1, 2, 3, 4, 1, 2, 5];
+        let requests = [1, 2, 3, 4, 1, 2, 5];
         let mut cache = CacheManager::new(3, CacheManagementStrategy::FWF);
-        let expected_costs = vec![1, 1, 1, 1, 1, 1, 1];
+        let expected_costs = [1, 1, 1, 1, 1, 1, 1];
 
         for (i, &req) in requests.iter().enumerate() {
             let cost = cache.access(req);
@@ -307,14 +1703,18 @@ mod tests {
     /// Tests the LRU strategy for correctness.
     #[test]
     fn test_lru_strategy() {
-        let requests = vec![1, 2, 3, 1, 4, 5];
+        let requests = [1, 2, 3, 1, 4, 5];
         let mut cache = CacheManager {
-            strategy: CacheManagementStrategy::LRU(VecDeque::new()),
+            strategy: CacheManagementStrategy::LRU,
             capacity: 3,
-            memory: VecDeque::with_capacity(3),
+            memory: PageList::with_capacity(3),
+            sizes: HashMap::new(),
+            used: 0,
+            time: 0,
+            observer: None,
         };
 
-        let expected_costs = vec![1, 1, 1, 0, 1, 1];
+        let expected_costs = [1, 1, 1, 0, 1, 1];
 
         for (i, &req) in requests.iter().enumerate() {
             let cost = cache.access(req);
@@ -325,11 +1725,15 @@ mod tests {
     /// Tests the LFU strategy for correctness.
     #[test]
     fn test_lfu_strategy() {
-        let requests = vec![1, 2, 1, 3, 4, 1, 5];
+        let requests = [1, 2, 1, 3, 4, 1, 5];
         let mut cache = CacheManager {
-            strategy: CacheManagementStrategy::LFU(HashMap::new()),
+            strategy: CacheManagementStrategy::LFU(Frequencies::new()),
             capacity: 3,
-            memory: VecDeque::with_capacity(3),
+            memory: PageList::with_capacity(3),
+            sizes: HashMap::new(),
+            used: 0,
+            time: 0,
+            observer: None,
         };
 
         // Expected behavior:
@@ -337,7 +1741,7 @@ mod tests {
         // - 4 replaces 2 (freq 1)
         // - 1 hit again (freq 3)
         // - 5 replaces 3 (freq 1)
-        let expected_costs = vec![1, 1, 0, 1, 1, 0, 1];
+        let expected_costs = [1, 1, 0, 1, 1, 0, 1];
 
         for (i, &req) in requests.iter().enumerate() {
             let cost = cache.access(req);
@@ -345,11 +1749,27 @@ mod tests {
         }
     }
 
+    /// Tests that LFU evicts the earliest loaded page among pages with equal counts.
+    #[test]
+    fn test_lfu_tie_break_by_load_order() {
+        let mut cache = CacheManager::new(2, CacheManagementStrategy::LFU(Frequencies::new()));
+        for page_id in [1, 2, 2, 1] {
+            cache.access(page_id);
+        }
+
+        // Both pages have been requested twice, but 2 reached that count first.
+        assert_eq!(cache.access(3), 1);
+        assert!(!cache.memory.contains(1));
+        assert!(cache.memory.contains(2));
+        assert!(cache.memory.contains(3));
+    }
+
     /// Tests the Random strategy for correctness.
     #[test]
     fn test_random_strategy() {
         // Create a cache with RAND strategy using its own RNG.
-        let mut cache = CacheManager::new(3, CacheManagementStrategy::RAND(rng()));
+        let mut cache =
+            CacheManager::new(3, CacheManagementStrategy::RAND(StdRng::seed_from_u64(0)));
         // First access is a miss.
         assert_eq!(cache.access(1), 1);
         // Second access to the same page is a hit.
@@ -367,7 +1787,12 @@ mod tests {
 
     #[test]
     fn test_rma_strategy() {
-        let mut cache = CacheManager::new(3, CacheManagementStrategy::RMA(HashMap::new(), rng()));
+        let mut cache = CacheManager::new(
+            3,
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::Random(
+                StdRng::seed_from_u64(0),
+            ))),
+        );
         // First access: miss, add and mark.
         assert_eq!(cache.access(1), 1);
         // Second access: hit, already marked.
@@ -380,4 +1805,547 @@ mod tests {
         // Page 4 should now be in the cache.
         assert_eq!(cache.access(4), 0);
     }
+
+    /// Tests marking with FIFO among the unmarked pages for correctness.
+    #[test]
+    fn test_marking_fifo_strategy() {
+        let requests = [1, 2, 3, 1, 4, 2, 5, 1];
+        let mut cache = CacheManager::new(
+            3,
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::FIFO)),
+        );
+
+        // Expected behavior:
+        // - 1, 2, 3 fill the cache and 1 is marked again
+        // - 4 starts a phase and evicts 1, the oldest page, although it was just requested
+        // - 2 hits and 5 evicts 3, the only unmarked page
+        // - 1 starts a phase and evicts 2, the oldest page
+        let expected_costs = [1, 1, 1, 0, 1, 0, 1, 1];
+
+        for (i, &req) in requests.iter().enumerate() {
+            let cost = cache.access(req);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+        let starts: Vec<usize> = cache.phases().unwrap().iter().map(|p| p.start).collect();
+        assert_eq!(starts, [0, 4, 7]);
+    }
+
+    /// Tests that marking with LRU among the unmarked pages makes the same choices as LRU.
+    #[test]
+    fn test_marking_lru_is_lru() {
+        let requests: Vec<usize> = (0..2000).map(|i| (i * i * 7 + i * 3 + 1) % 20).collect();
+        let mut lru = CacheManager::new(5, CacheManagementStrategy::LRU);
+        let mut marking = CacheManager::new(
+            5,
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::LRU)),
+        );
+        for &req in &requests {
+            assert_eq!(lru.access(req), marking.access(req));
+        }
+    }
+
+    /// Tests that layered marking evicts from the deepest layer of the configurations the
+    /// optimum can be in, where RMA picks any unmarked page.
+    #[test]
+    fn test_layered_strategy() {
+        // With k = 2, after 1, 2, 3, 4 the optimum holds 4 and one of {1, 2, 3}, and only 3
+        // of those is still cached. Both 3 and 4 are unmarked when 1 starts the third phase,
+        // but layered marking must evict 3 to stay in a configuration the optimum can be in, and
+        // then hits on 4.
+        let requests = [1, 2, 3, 4, 1, 4];
+        let costs = |eviction: MarkingEviction| {
+            let mut cache = CacheManager::new(
+                2,
+                CacheManagementStrategy::Marking(MarkingPhases::new(eviction)),
+            );
+            requests
+                .iter()
+                .map(|&req| cache.access(req))
+                .collect::<Vec<usize>>()
+        };
+        for seed in 0..20 {
+            assert_eq!(
+                costs(MarkingEviction::Layered(
+                    Layers::new(),
+                    StdRng::seed_from_u64(seed)
+                )),
+                [1, 1, 1, 1, 1, 0],
+                "Layered marking did not evict 3 with seed {}",
+                seed
+            );
+        }
+        assert!(
+            (0..20).any(|seed| {
+                costs(MarkingEviction::Random(StdRng::seed_from_u64(seed))) == [1, 1, 1, 1, 1, 1]
+            }),
+            "RMA never evicted 4"
+        );
+    }
+
+    /// Tests that an observer receives one event per access.
+    #[test]
+    fn test_observer() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut cache = CacheManager::new(2, CacheManagementStrategy::FIFO);
+        cache.access(1);
+        cache.set_observer(move |event: &AccessEvent| sender.send(event.clone()).unwrap());
+        for req in [2, 1, 3, 1] {
+            cache.access(req);
+        }
+        let snapshot = cache.snapshot();
+        drop(cache);
+
+        let events: Vec<AccessEvent> = receiver.iter().collect();
+        let summary: Vec<(usize, usize, bool, Vec<usize>)> = events
+            .iter()
+            .map(|e| (e.time, e.page_id, e.hit, e.evicted.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, 2, false, vec![]),
+                (2, 1, true, vec![]),
+                (3, 3, false, vec![1]),
+                (4, 1, false, vec![2]),
+            ]
+        );
+        assert_eq!(events[3].snapshot, snapshot);
+
+        // Snapshots compare contents regardless of order or strategy.
+        let mut lru = CacheManager::new(2, CacheManagementStrategy::LRU);
+        for req in [1, 3] {
+            lru.access(req);
+        }
+        assert_eq!(lru.snapshot(), snapshot);
+        assert_ne!(events[2].snapshot, snapshot);
+    }
+
+    /// Tests the MIN strategy for correctness.
+    #[test]
+    fn test_min_strategy() {
+        let requests = [1, 2, 3, 4, 1, 2, 5, 1, 2, 3, 4, 5];
+        let mut cache = CacheManager::new(3, CacheManagementStrategy::MIN(NextUse::new(&requests)));
+
+        // Expected behavior:
+        // - 1, 2, 3 fill the cache
+        // - 4 replaces 3 (requested furthest in the future)
+        // - 1, 2 hit
+        // - 5 replaces 4 (requested furthest in the future)
+        // - 1, 2 hit
+        // - 3 and 4 replace pages that are never requested again
+        // - 5 hits
+        let expected_costs = [1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 1, 0];
+
+        for (i, &req) in requests.iter().enumerate() {
+            let cost = cache.access(req);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    /// Tests that no online strategy beats MIN on the same sequence.
+    #[test]
+    fn test_min_not_above_online() {
+        let requests: Vec<usize> = (0..2000).map(|i| (i * i * 7 + i * 3 + 1) % 20).collect();
+        let serve = |strategy| {
+            let mut cache = CacheManager::new(5, strategy);
+            requests.iter().map(|&req| cache.access(req)).sum::<usize>()
+        };
+        let opt = serve(CacheManagementStrategy::MIN(NextUse::new(&requests)));
+        for strategy in [
+            CacheManagementStrategy::FIFO,
+            CacheManagementStrategy::FWF,
+            CacheManagementStrategy::LRU,
+            CacheManagementStrategy::LFU(Frequencies::new()),
+            CacheManagementStrategy::RAND(StdRng::seed_from_u64(0)),
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::Random(
+                StdRng::seed_from_u64(0),
+            ))),
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::Layered(
+                Layers::new(),
+                StdRng::seed_from_u64(0),
+            ))),
+        ] {
+            let name = format!("{:?}", strategy);
+            let cost = serve(strategy);
+            assert!(opt <= cost, "{} beat MIN: {} < {}", name, cost, opt);
+        }
+    }
+
+    /// Tests LRU with lookahead against LRU, MIN and the two lookahead models.
+    #[test]
+    fn test_lookahead_strategy() {
+        let requests: Vec<usize> = (0..2000).map(|i| (i * i * 7 + i * 3 + 1) % 20).collect();
+        let serve = |strategy| {
+            let mut cache = CacheManager::new(5, strategy);
+            requests.iter().map(|&req| cache.access(req)).sum::<usize>()
+        };
+        let lru = serve(CacheManagementStrategy::LRU);
+        let opt = serve(CacheManagementStrategy::MIN(NextUse::new(&requests)));
+        for model in [LookaheadModel::Weak, LookaheadModel::Strong] {
+            let lookahead = |window| {
+                serve(CacheManagementStrategy::Lookahead(Lookahead::new(
+                    &requests, window, model, 0,
+                )))
+            };
+            assert_eq!(lookahead(0), lru, "{:?}", model);
+            assert_eq!(lookahead(requests.len()), opt, "{:?}", model);
+        }
+
+        // Weak lookahead of 2 only sees 3 repeated and evicts 1; strong lookahead of 1 sees
+        // past the repeats to 1 and evicts 2 instead.
+        let requests = [1, 2, 3, 3, 3, 1];
+        for (model, window, expected) in [
+            (LookaheadModel::Weak, 2, [1, 1, 1, 0, 0, 1]),
+            (LookaheadModel::Strong, 1, [1, 1, 1, 0, 0, 0]),
+        ] {
+            let mut cache = CacheManager::new(
+                2,
+                CacheManagementStrategy::Lookahead(Lookahead::new(&requests, window, model, 0)),
+            );
+            let costs: Vec<usize> = requests.iter().map(|&req| cache.access(req)).collect();
+            assert_eq!(costs, expected, "{:?}", model);
+        }
+    }
+
+    /// Tests that every strategy names itself and that a reset one serves a sequence as a new
+    /// one would.
+    #[test]
+    fn test_online_algorithm() {
+        let requests: Vec<usize> = (0..2000).map(|i| (i * 7 + i / 13) % 11 + 1).collect();
+        for strategy in every_strategy(&requests) {
+            let name = strategy.name();
+            assert!(!name.is_empty());
+            let randomized = matches!(
+                name,
+                "RAND" | "LAYERED" | "WRMA" | "PREDICTIVE-MARKER" | "LMARKER"
+            );
+            let mut cache = CacheManager::new(4, strategy);
+            let cost = online_core::run(&mut cache, requests.iter().copied());
+            let snapshot = OnlineAlgorithm::snapshot(&cache);
+            cache.reset();
+            assert_eq!(OnlineAlgorithm::snapshot(&cache), 0, "{}", name);
+            let again = online_core::run(&mut cache, requests.iter().copied());
+            assert_eq!(OnlineAlgorithm::name(&cache), name);
+            if !randomized {
+                assert_eq!(again, cost, "{}", name);
+                assert_eq!(OnlineAlgorithm::snapshot(&cache), snapshot, "{}", name);
+            }
+        }
+    }
+
+    /// Tests that prefetching turns a scan's misses into hits.
+    #[test]
+    fn test_prefetching() {
+        let requests: Vec<usize> = (1..=100).collect();
+        let mut cache = CacheManager::new(
+            4,
+            CacheManagementStrategy::Lookahead(Lookahead::new(
+                &requests,
+                3,
+                LookaheadModel::Weak,
+                3,
+            )),
+        );
+        // Every miss brings in the next 3 pages of the scan.
+        let cost: usize = requests.iter().map(|&req| cache.access(req)).sum();
+        assert_eq!(cost, 25);
+        assert_eq!(cache.prefetches(), Some(75));
+        assert_eq!(
+            CacheManager::new(4, CacheManagementStrategy::LRU).prefetches(),
+            None
+        );
+    }
+
+    /// Tests Follow the Prediction against MIN and the combiner against the strategies it
+    /// combines.
+    #[test]
+    fn test_prediction_strategies() {
+        let requests: Vec<usize> = (0..2000).map(|i| (i * i * 7 + i * 3 + 1) % 20).collect();
+        let serve = |strategy| {
+            let mut cache = CacheManager::new(5, strategy);
+            requests.iter().map(|&req| cache.access(req)).sum::<usize>()
+        };
+        let opt = serve(CacheManagementStrategy::MIN(NextUse::new(&requests)));
+        let oracle = Predictor::Oracle.predict(&requests);
+        assert_eq!(
+            serve(CacheManagementStrategy::FollowPrediction(oracle)),
+            opt
+        );
+
+        let noisy = || Predictor::Noisy(1000.0, StdRng::seed_from_u64(0)).predict(&requests);
+        let ftp = serve(CacheManagementStrategy::FollowPrediction(noisy()));
+        let lru = serve(CacheManagementStrategy::LRU);
+        let combined = serve(CacheManagementStrategy::Combiner(Box::new(
+            Combination::new(
+                CacheManager::new(5, CacheManagementStrategy::FollowPrediction(noisy())),
+                CacheManager::new(5, CacheManagementStrategy::LRU),
+            ),
+        )));
+        // Poor predictions make Follow the Prediction worse than LRU, and the combiner stays
+        // close to the better of the two.
+        assert!(lru < ftp, "{} >= {}", lru, ftp);
+        assert!(combined <= 2 * lru, "{} > 2 * {}", combined, lru);
+    }
+
+    /// Tests that a combiner cannot simulate caches larger than its own.
+    #[test]
+    #[should_panic(expected = "Combined caches must not be larger than the cache")]
+    fn test_combiner_capacity() {
+        CacheManager::new(
+            4,
+            CacheManagementStrategy::Combiner(Box::new(Combination::new(
+                CacheManager::new(5, CacheManagementStrategy::LRU),
+                CacheManager::new(5, CacheManagementStrategy::FIFO),
+            ))),
+        );
+    }
+
+    /// Tests the GreedyDual strategy for correctness.
+    #[test]
+    fn test_greedy_dual_strategy() {
+        let requests = [(1, 10.0), (2, 1.0), (3, 1.0), (2, 1.0), (1, 10.0)];
+        let mut cache = CacheManager::new(2, CacheManagementStrategy::GreedyDual(Credits::new()));
+
+        // Expected behavior:
+        // - 1, 2 fill the cache
+        // - 3 replaces 2 (credit 1 < 10), the remaining credit of 1 drops to 9
+        // - 2 replaces 3 (credit 1 < 9)
+        // - 1 hits although it is the least recently used page
+        let expected_costs = [10.0, 1.0, 1.0, 1.0, 0.0];
+
+        for (i, &(req, weight)) in requests.iter().enumerate() {
+            let cost = cache.access_sized(req, 1, weight);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    #[test]
+    fn test_wrma_strategy() {
+        let mut cache = CacheManager::new(
+            2,
+            CacheManagementStrategy::WRMA(
+                PageList::new(),
+                HashMap::new(),
+                StdRng::seed_from_u64(0),
+            ),
+        );
+        // Fill the cache with an expensive and a cheap page.
+        assert_eq!(cache.access_sized(1, 1, 1e12), 1e12);
+        assert_eq!(cache.access_sized(2, 1, 1.0), 1.0);
+        // Cache is full and all pages are marked; the cheap page is all but certain to go.
+        assert_eq!(cache.access_sized(3, 1, 1.0), 1.0);
+        assert_eq!(cache.access_sized(1, 1, 1e12), 0.0);
+        assert_eq!(cache.access_sized(3, 1, 1.0), 0.0);
+    }
+
+    /// Tests that zero fetch costs are rejected before WRMA divides by them.
+    #[test]
+    #[should_panic(expected = "Fetch costs must be positive and finite")]
+    fn test_zero_weight() {
+        let mut cache = CacheManager::new(
+            2,
+            CacheManagementStrategy::WRMA(
+                PageList::new(),
+                HashMap::new(),
+                StdRng::seed_from_u64(0),
+            ),
+        );
+        cache.access_sized(1, 1, 0.0);
+    }
+
+    /// Tests the size-aware LRU strategy for correctness.
+    #[test]
+    fn test_size_lru_strategy() {
+        let requests = [(1, 4), (2, 4), (3, 4), (2, 4), (4, 8), (5, 11), (4, 8)];
+        let mut cache = CacheManager::new(10, CacheManagementStrategy::SizeLRU);
+
+        // Expected behavior:
+        // - 1, 2 fill 8 of 10 bytes
+        // - 3 replaces 1, 2 hits
+        // - 4 needs 8 bytes and replaces both 3 and 2
+        // - 5 is larger than the cache and bypasses it
+        // - 4 hits
+        let expected_costs = [1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+
+        for (i, &(req, size)) in requests.iter().enumerate() {
+            let cost = cache.access_sized(req, size, 1.0);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    /// Tests the GDSF strategy for correctness.
+    #[test]
+    fn test_gdsf_strategy() {
+        let requests = [1, 1, 2, 3, 1, 2, 3];
+        let mut cache = CacheManager::new(
+            10,
+            CacheManagementStrategy::GDSF(Credits::new(), HashMap::new()),
+        );
+
+        // Expected behavior (every object has 5 bytes):
+        // - 1 is requested twice, 2 fills the cache
+        // - 3 replaces 2, which was requested only once
+        // - 1 hits
+        // - 2 replaces 3, and 3 replaces 2 again, as 1 keeps the most credit
+        let expected_costs = [1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0];
+
+        for (i, &req) in requests.iter().enumerate() {
+            let cost = cache.access_sized(req, 5, 1.0);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    /// Tests the Landlord strategy with full and without credit refresh on hits.
+    #[test]
+    fn test_landlord_strategy() {
+        let requests = [(1, 2.0), (2, 1.0), (3, 1.5), (1, 2.0), (4, 1.0), (1, 2.0)];
+        // With a full refresh, the hit restores 1's credit and 3 is evicted for 4;
+        // without one, 1 keeps the credit it had left and is evicted instead.
+        for (refresh, expected_costs) in [
+            (1.0, [2.0, 1.0, 1.5, 0.0, 1.0, 0.0]),
+            (0.0, [2.0, 1.0, 1.5, 0.0, 1.0, 2.0]),
+        ] {
+            let mut cache = CacheManager::new(
+                2,
+                CacheManagementStrategy::Landlord(Credits::new(), refresh),
+            );
+            for (i, &(req, weight)) in requests.iter().enumerate() {
+                let cost = cache.access_sized(req, 1, weight);
+                assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+            }
+        }
+    }
+
+    /// Tests the CLOCK strategy for correctness.
+    #[test]
+    fn test_clock_strategy() {
+        let requests = [1, 2, 3, 1, 4, 1, 5, 1];
+        let mut cache = CacheManager::new(3, CacheManagementStrategy::CLOCK(HashSet::new()));
+
+        // 4 clears 1's reference bit and evicts 2; 5 evicts the unreferenced 3.
+        let expected_costs = [1, 1, 1, 0, 1, 0, 1, 0];
+
+        for (i, &req) in requests.iter().enumerate() {
+            let cost = cache.access(req);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    /// Tests the 2Q strategy for correctness.
+    #[test]
+    fn test_two_q_strategy() {
+        let requests = [1, 2, 3, 4, 5, 1, 5, 1, 6, 3, 7, 2, 1];
+        let mut cache = CacheManager::new(4, CacheManagementStrategy::TwoQ(TwoQueues::new()));
+
+        // 1 and 3 return while remembered in A1out and are promoted to Am, where they survive
+        // the scan of 6, 7 and 2 through A1in; 2 was forgotten and is admitted to A1in again.
+        let expected_costs = [1, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 0];
+
+        for (i, &req) in requests.iter().enumerate() {
+            let cost = cache.access(req);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    /// Tests the ARC strategy for correctness.
+    #[test]
+    fn test_arc_strategy() {
+        let requests = [1, 2, 1, 3, 2, 1, 2, 3];
+        let mut cache = CacheManager::new(2, CacheManagementStrategy::ARC(AdaptiveLists::new()));
+
+        // 3 pushes 2 into the B1 ghost list; the ghost hits on 2, 1 and 3 then move the
+        // target size of T1 and decide whether T1 or T2 gives up a page.
+        let expected_costs = [1, 1, 0, 1, 1, 1, 0, 1];
+
+        for (i, &req) in requests.iter().enumerate() {
+            let cost = cache.access(req);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    /// Tests the LIRS strategy for correctness.
+    #[test]
+    fn test_lirs_strategy() {
+        let requests = [1, 2, 3, 1, 4, 3, 2, 4, 1];
+        let mut cache = CacheManager::new(3, CacheManagementStrategy::LIRS(LirsStack::new()));
+
+        // 1 and 2 become LIR and 3 is the only HIR slot. 3 and 4 return while still in the
+        // stack, so each becomes LIR and demotes the LIR page at the bottom of the stack.
+        let expected_costs = [1, 1, 1, 0, 1, 1, 0, 1, 0];
+
+        for (i, &req) in requests.iter().enumerate() {
+            let cost = cache.access(req);
+            assert_eq!(cost, expected_costs[i], "Mismatch at request index {}", i);
+        }
+    }
+
+    /// Creates one instance of every strategy, those built from a request sequence for the
+    /// given requests.
+    fn every_strategy(requests: &[usize]) -> Vec<CacheManagementStrategy> {
+        vec![
+            CacheManagementStrategy::FIFO,
+            CacheManagementStrategy::FWF,
+            CacheManagementStrategy::LRU,
+            CacheManagementStrategy::LFU(Frequencies::new()),
+            CacheManagementStrategy::RAND(StdRng::seed_from_u64(0)),
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::Layered(
+                Layers::new(),
+                StdRng::seed_from_u64(0),
+            ))),
+            CacheManagementStrategy::MIN(NextUse::new(requests)),
+            CacheManagementStrategy::GreedyDual(Credits::new()),
+            CacheManagementStrategy::WRMA(
+                PageList::new(),
+                HashMap::new(),
+                StdRng::seed_from_u64(0),
+            ),
+            CacheManagementStrategy::GDSF(Credits::new(), HashMap::new()),
+            CacheManagementStrategy::Landlord(Credits::new(), 0.5),
+            CacheManagementStrategy::CLOCK(HashSet::new()),
+            CacheManagementStrategy::TwoQ(TwoQueues::new()),
+            CacheManagementStrategy::ARC(AdaptiveLists::new()),
+            CacheManagementStrategy::LIRS(LirsStack::new()),
+            CacheManagementStrategy::Lookahead(Lookahead::new(
+                requests,
+                3,
+                LookaheadModel::Strong,
+                2,
+            )),
+            CacheManagementStrategy::FollowPrediction(Predictor::Oracle.predict(requests)),
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::Predictive(
+                Chains::new(Predictor::Oracle.predict(requests)),
+                StdRng::seed_from_u64(0),
+            ))),
+            CacheManagementStrategy::Marking(MarkingPhases::new(MarkingEviction::LMarker(
+                Predictor::Oracle.predict(requests),
+                StdRng::seed_from_u64(0),
+            ))),
+            CacheManagementStrategy::Combiner(Box::new(Combination::new(
+                CacheManager::new(4, CacheManagementStrategy::LRU),
+                CacheManager::new(4, CacheManagementStrategy::FIFO),
+            ))),
+        ]
+    }
+
+    /// Tests that every strategy forgets invalidated pages and keeps serving within capacity.
+    #[test]
+    fn test_invalidate() {
+        let requests: Vec<usize> = (0..2000).map(|i| (i * 7 + i / 13) % 11 + 1).collect();
+        let strategies = every_strategy(&requests);
+        for strategy in strategies {
+            let name = format!("{:?}", strategy);
+            let mut cache = CacheManager::new(4, strategy);
+            for (t, &page) in requests.iter().enumerate() {
+                let (cost, evicted) = cache.access_with_evictions(page);
+                assert!(cache.contains(page), "{}", name);
+                assert!(evicted.len() <= cost * 4, "{}", name);
+                if t % 3 == 0 {
+                    assert!(cache.invalidate(page), "{}", name);
+                    assert!(!cache.invalidate(page), "{}", name);
+                    assert!(!cache.contains(page), "{}", name);
+                }
+                assert!(cache.memory.len() <= 4, "{}", name);
+            }
+        }
+    }
 }
@@ -0,0 +1,222 @@
+use crate::cache::CacheManager;
+
+/// Enum representing how the levels of a `CacheHierarchy` share pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InclusionPolicy {
+    /// Every level holds a copy of each page cached above it. A page fetched into a level is
+    /// copied into every level above it, and a page evicted from a level is invalidated in
+    /// every level above it.
+    Inclusive,
+    /// Every page is cached in at most one level. A page is served into the first level,
+    /// pages evicted from a level are demoted into the next one, and pages evicted from the
+    /// last level are dropped.
+    Exclusive,
+}
+
+/// A level of a `CacheHierarchy`.
+#[derive(Debug)]
+struct Level {
+    /// The cache of the level.
+    cache: CacheManager,
+    /// The latency of serving a request from the level.
+    latency: f64,
+    /// The number of requests served from the level.
+    hits: usize,
+}
+
+/// A chain of cache levels, first level first, in front of a backing memory.
+///
+/// Each level runs its own strategy with its own capacity. A request is looked up level by
+/// level and costs the latency of the level that serves it, or the memory latency if none
+/// does. Lower levels only see the requests that miss above them, or the pages demoted into
+/// them, so MIN, which needs its request sequence up front, cannot run below the first level.
+#[derive(Debug)]
+pub struct CacheHierarchy {
+    /// The cache levels, first level first.
+    levels: Vec<Level>,
+    /// How the levels share pages.
+    policy: InclusionPolicy,
+    /// The latency of serving a request from memory.
+    memory_latency: f64,
+    /// The number of requests served from memory.
+    memory_hits: usize,
+    /// The total latency of the requests served so far.
+    total_latency: f64,
+}
+
+impl CacheHierarchy {
+    /// Creates a new `CacheHierarchy` with no cache levels.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - How the levels share pages.
+    /// * `memory_latency` - The latency of serving a request from memory.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `CacheHierarchy`.
+    pub fn new(policy: InclusionPolicy, memory_latency: f64) -> Self {
+        CacheHierarchy {
+            levels: Vec::new(),
+            policy,
+            memory_latency,
+            memory_hits: 0,
+            total_latency: 0.0,
+        }
+    }
+
+    /// Appends a level below the existing ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache of the level, with its strategy and capacity.
+    /// * `latency` - The latency of serving a request from the level.
+    pub fn push_level(&mut self, cache: CacheManager, latency: f64) {
+        self.levels.push(Level {
+            cache,
+            latency,
+            hits: 0,
+        });
+    }
+
+    /// Returns the number of requests served from each level, first level first, followed by
+    /// the number served from memory.
+    pub fn hits(&self) -> Vec<usize> {
+        self.levels
+            .iter()
+            .map(|level| level.hits)
+            .chain([self.memory_hits])
+            .collect()
+    }
+
+    /// Returns the total latency of the requests served so far.
+    pub fn total_latency(&self) -> f64 {
+        self.total_latency
+    }
+
+    /// Returns `true` if the page is cached in the level with the given index.
+    #[allow(dead_code)]
+    pub fn contains(&self, level: usize, page_id: usize) -> bool {
+        self.levels[level].cache.contains(page_id)
+    }
+
+    /// Serves a request through the hierarchy.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The latency of the level that served the request, or the memory latency.
+    pub fn access(&mut self, page_id: usize) -> f64 {
+        let found = self
+            .levels
+            .iter()
+            .position(|level| level.cache.contains(page_id));
+        let latency = match found {
+            Some(index) => {
+                self.levels[index].hits += 1;
+                self.levels[index].latency
+            }
+            None => {
+                self.memory_hits += 1;
+                self.memory_latency
+            }
+        };
+        self.total_latency += latency;
+
+        match self.policy {
+            InclusionPolicy::Inclusive => self._inclusive_fill(page_id, found),
+            InclusionPolicy::Exclusive => self._exclusive_fill(page_id, found),
+        }
+        latency
+    }
+
+    /// Copies the page into every level down to the one that served it, deepest first, and
+    /// invalidates the pages each level evicts in every level above it.
+    fn _inclusive_fill(&mut self, page_id: usize, found: Option<usize>) {
+        let deepest = found.unwrap_or(self.levels.len().saturating_sub(1));
+        for index in (0..self.levels.len().min(deepest + 1)).rev() {
+            let (_, evicted) = self.levels[index].cache.access_with_evictions(page_id);
+            for victim in evicted {
+                for level in &mut self.levels[..index] {
+                    level.cache.invalidate(victim);
+                }
+            }
+        }
+    }
+
+    /// Moves the page into the first level and demotes the evicted pages level by level.
+    fn _exclusive_fill(&mut self, page_id: usize, found: Option<usize>) {
+        if let Some(index) = found
+            && index > 0
+        {
+            self.levels[index].cache.invalidate(page_id);
+        }
+        let mut incoming = vec![page_id];
+        for level in &mut self.levels {
+            let mut evicted = Vec::new();
+            for page in incoming {
+                evicted.extend(level.cache.access_with_evictions(page).1);
+            }
+            if evicted.is_empty() {
+                break;
+            }
+            incoming = evicted;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheManagementStrategy;
+
+    /// Creates a hierarchy of an LRU level of 2 pages over an LRU level of 4 pages.
+    fn two_levels(policy: InclusionPolicy) -> CacheHierarchy {
+        let mut hierarchy = CacheHierarchy::new(policy, 100.0);
+        hierarchy.push_level(CacheManager::new(2, CacheManagementStrategy::LRU), 1.0);
+        hierarchy.push_level(CacheManager::new(4, CacheManagementStrategy::LRU), 10.0);
+        hierarchy
+    }
+
+    #[test]
+    fn test_inclusive_hierarchy() {
+        let mut hierarchy = two_levels(InclusionPolicy::Inclusive);
+        let latencies: Vec<f64> = [1, 2, 1, 3, 1, 4, 1, 5, 1]
+            .iter()
+            .map(|&page| hierarchy.access(page))
+            .collect();
+        // 1 stays in the first level, but the second level never sees its hits and evicts it
+        // to make room for 5, which invalidates it in the first level too.
+        assert_eq!(
+            latencies,
+            [100.0, 100.0, 1.0, 100.0, 1.0, 100.0, 1.0, 100.0, 100.0]
+        );
+        assert_eq!(hierarchy.hits(), [3, 0, 6]);
+        assert_eq!(hierarchy.total_latency(), 603.0);
+        for page in 1..=5 {
+            assert!(!hierarchy.contains(0, page) || hierarchy.contains(1, page));
+        }
+    }
+
+    #[test]
+    fn test_exclusive_hierarchy() {
+        let mut hierarchy = two_levels(InclusionPolicy::Exclusive);
+        let latencies: Vec<f64> = [1, 2, 3, 4, 5, 6, 1, 7, 1]
+            .iter()
+            .map(|&page| hierarchy.access(page))
+            .collect();
+        // The levels hold 6 distinct pages together, so 1 is still in the second level
+        // after 6 and comes back to the first level.
+        assert_eq!(
+            latencies,
+            [100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 10.0, 100.0, 1.0]
+        );
+        assert_eq!(hierarchy.hits(), [1, 1, 7]);
+        for page in 1..=7 {
+            assert!(!(hierarchy.contains(0, page) && hierarchy.contains(1, page)));
+        }
+    }
+}
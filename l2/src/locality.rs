@@ -0,0 +1,315 @@
+use rand::distr::weighted::WeightedIndex;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+/// An undirected access graph over the pages `1..=n`, as in the access graph model of
+/// Borodin, Irani, Raghavan and Schieber: a request may only follow the previous one along
+/// an edge.
+#[derive(Debug, Clone)]
+pub struct AccessGraph {
+    /// The neighbours of page `p` at index `p - 1`.
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl AccessGraph {
+    /// Creates an access graph from a list of undirected edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of pages.
+    /// * `edges` - The edges, as pairs of pages from `1..=n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has an endpoint outside `1..=n`.
+    pub fn from_edges(n: usize, edges: &[(usize, usize)]) -> Self {
+        let mut neighbours = vec![Vec::new(); n];
+        for &(u, v) in edges {
+            assert!(
+                (1..=n).contains(&u) && (1..=n).contains(&v),
+                "Edge ({}, {}) has an endpoint outside 1..={}",
+                u,
+                v,
+                n
+            );
+            if !neighbours[u - 1].contains(&v) {
+                neighbours[u - 1].push(v);
+            }
+            if !neighbours[v - 1].contains(&u) {
+                neighbours[v - 1].push(u);
+            }
+        }
+        AccessGraph { neighbours }
+    }
+
+    /// Creates the path `1 - 2 - .. - n`.
+    pub fn path(n: usize) -> Self {
+        let edges: Vec<(usize, usize)> = (1..n).map(|page| (page, page + 1)).collect();
+        AccessGraph::from_edges(n, &edges)
+    }
+
+    /// Creates the cycle `1 - 2 - .. - n - 1`.
+    pub fn cycle(n: usize) -> Self {
+        let edges: Vec<(usize, usize)> = (1..=n).map(|page| (page, page % n + 1)).collect();
+        AccessGraph::from_edges(n, &edges)
+    }
+
+    /// Creates the complete tree with the given arity on `n` pages, rooted at page 1, whose
+    /// levels are filled left to right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arity` is 0.
+    pub fn tree(n: usize, arity: usize) -> Self {
+        assert!(arity > 0, "A tree needs an arity of at least 1");
+        let edges: Vec<(usize, usize)> =
+            (2..=n).map(|page| ((page - 2) / arity + 1, page)).collect();
+        AccessGraph::from_edges(n, &edges)
+    }
+
+    /// Reads an access graph from a text edge list: one edge per line as two whitespace
+    /// separated pages. Empty lines and lines starting with `#` are ignored. The graph spans
+    /// the pages `1..=n`, with `n` the largest page of any edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The buffered edge list.
+    ///
+    /// # Returns
+    ///
+    /// The access graph, or an error for malformed input.
+    pub fn read_edges<R: BufRead>(input: R) -> io::Result<Self> {
+        let mut edges = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            let record = line.trim();
+            if record.is_empty() || record.starts_with('#') {
+                continue;
+            }
+            let pages: Vec<usize> = record
+                .split_whitespace()
+                .map(|page| page.parse().ok().filter(|&page| page > 0))
+                .collect::<Option<_>>()
+                .filter(|pages: &Vec<usize>| pages.len() == 2)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid edge {:?} on line {}", record, index + 1),
+                    )
+                })?;
+            edges.push((pages[0], pages[1]));
+        }
+        let n = edges.iter().map(|&(u, v)| u.max(v)).max().unwrap_or(0);
+        Ok(AccessGraph::from_edges(n, &edges))
+    }
+
+    /// Returns the number of pages.
+    pub fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    /// Returns `true` if the graph has no pages.
+    pub fn is_empty(&self) -> bool {
+        self.neighbours.is_empty()
+    }
+
+    /// Returns the neighbours of a page, or no pages for a page outside the graph.
+    pub fn neighbours(&self, page_id: usize) -> &[usize] {
+        page_id
+            .checked_sub(1)
+            .and_then(|index| self.neighbours.get(index))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Computes the distance of every page to the nearest of the `sources` by breadth-first
+    /// search, in O(n + m) time for a graph of `n` pages and `m` edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - The pages to measure distances from. Pages outside the graph are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The distance of page `p` at index `p - 1`, or `None` if no source reaches it.
+    pub fn distances(&self, sources: impl IntoIterator<Item = usize>) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        for page_id in sources {
+            if let Some(distance @ None) = page_id
+                .checked_sub(1)
+                .and_then(|index| distances.get_mut(index))
+            {
+                *distance = Some(0);
+                queue.push_back(page_id);
+            }
+        }
+        while let Some(page_id) = queue.pop_front() {
+            let next = distances[page_id - 1].map(|distance| distance + 1);
+            for &neighbour in self.neighbours(page_id) {
+                if distances[neighbour - 1].is_none() {
+                    distances[neighbour - 1] = next;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        distances
+    }
+}
+
+/// A request generator following a Markov chain over the pages `1..=n`: each request is
+/// drawn from a distribution that depends on the previous request only.
+#[derive(Debug)]
+pub struct MarkovChain {
+    /// The possible successors of page `p` at index `p - 1`.
+    successors: Vec<Vec<usize>>,
+    /// The distribution over `successors` of page `p` at index `p - 1`.
+    transitions: Vec<WeightedIndex<f64>>,
+    /// The previous request.
+    current: usize,
+    /// The random number generator.
+    rng: StdRng,
+}
+
+impl MarkovChain {
+    /// Creates a Markov chain from the transition weights of every page. The first request
+    /// is drawn uniformly at random.
+    ///
+    /// # Arguments
+    ///
+    /// * `transitions` - For page `p` at index `p - 1`, the successors of `p` with their
+    ///   weights, which need not add up to 1.
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a page has no successor of positive weight, or a successor outside `1..=n`.
+    pub fn new(transitions: Vec<Vec<(usize, f64)>>, seed: u64) -> Self {
+        let n = transitions.len();
+        let (successors, transitions) = transitions
+            .into_iter()
+            .map(|row| {
+                assert!(
+                    row.iter().all(|&(page_id, _)| (1..=n).contains(&page_id)),
+                    "Successors must lie in 1..={}",
+                    n
+                );
+                let (successors, weights): (Vec<usize>, Vec<f64>) = row.into_iter().unzip();
+                let distribution = WeightedIndex::new(weights)
+                    .expect("Every page needs a successor of positive weight");
+                (successors, distribution)
+            })
+            .unzip();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let current = rng.random_range(1..=n);
+        MarkovChain {
+            successors,
+            transitions,
+            current,
+            rng,
+        }
+    }
+
+    /// Creates a random walk on an access graph, moving to a neighbour chosen uniformly at
+    /// random at each step.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The access graph, which must have no isolated page.
+    /// * `seed` - The seed of the random number generator.
+    pub fn random_walk(graph: &AccessGraph, seed: u64) -> Self {
+        let transitions = (1..=graph.len())
+            .map(|page_id| {
+                graph
+                    .neighbours(page_id)
+                    .iter()
+                    .map(|&neighbour| (neighbour, 1.0))
+                    .collect()
+            })
+            .collect();
+        MarkovChain::new(transitions, seed)
+    }
+
+    /// Creates a chain on the cycle of `n` pages that jumps from page `p` to page
+    /// `p + d (mod n)` with weight `1 / (|d| + 1)^exponent`, so nearby pages are likely to
+    /// follow each other while any page can be reached in one step.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of pages.
+    /// * `exponent` - How fast the weight decays with the distance.
+    /// * `seed` - The seed of the random number generator.
+    pub fn distance_decay(n: usize, exponent: f64, seed: u64) -> Self {
+        let transitions = (0..n)
+            .map(|from| {
+                (0..n)
+                    .map(|to| {
+                        let distance = (to + n - from) % n;
+                        let distance = distance.min(n - distance);
+                        (to + 1, 1.0 / ((distance + 1) as f64).powf(exponent))
+                    })
+                    .collect()
+            })
+            .collect();
+        MarkovChain::new(transitions, seed)
+    }
+
+    /// Samples the next request from {1, 2, …, n}.
+    pub fn sample(&mut self) -> usize {
+        let index = self.current - 1;
+        self.current = self.successors[index][self.transitions[index].sample(&mut self.rng)];
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_graph() {
+        assert_eq!(AccessGraph::path(4).neighbours(2), [1, 3]);
+        assert_eq!(AccessGraph::cycle(4).neighbours(1), [2, 4]);
+        let tree = AccessGraph::tree(7, 2);
+        assert_eq!(tree.neighbours(1), [2, 3]);
+        assert_eq!(tree.neighbours(3), [1, 6, 7]);
+        assert_eq!(tree.neighbours(8), [] as [usize; 0]);
+
+        // Distances to the nearest of pages 4 and 5 in the tree, with page 9 ignored.
+        let distances: Vec<usize> = tree.distances([4, 5, 9]).into_iter().flatten().collect();
+        assert_eq!(distances, [2, 1, 3, 0, 0, 4, 4]);
+
+        let graph = AccessGraph::read_edges("# A triangle\n1 2\n2 3\n\n3 1\n".as_bytes()).unwrap();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.neighbours(3), [2, 1]);
+        assert!(AccessGraph::read_edges("1 2\n2 x\n".as_bytes()).is_err());
+        assert!(AccessGraph::read_edges("1 0\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_markov_chain() {
+        let graph = AccessGraph::path(10);
+        let mut walk = MarkovChain::random_walk(&graph, 0);
+        let mut previous = walk.sample();
+        for _ in 0..1000 {
+            let page_id = walk.sample();
+            assert!(graph.neighbours(previous).contains(&page_id));
+            previous = page_id;
+        }
+
+        // The chain mostly stays close to the previous request.
+        let mut chain = MarkovChain::distance_decay(100, 2.0, 0);
+        let mut previous = chain.sample();
+        let mut near = 0;
+        for _ in 0..1000 {
+            let page_id = chain.sample();
+            let distance = page_id.abs_diff(previous);
+            if distance.min(100 - distance) <= 2 {
+                near += 1;
+            }
+            previous = page_id;
+        }
+        assert!(near > 700, "only {} of 1000 steps were near", near);
+    }
+}
@@ -1,112 +1,1437 @@
+mod adversary;
+use adversary::Adversary;
+
 mod cache;
-use cache::{CacheManagementStrategy, CacheManager};
+use cache::{
+    AccessEvent, AdaptiveLists, CacheManagementStrategy, CacheManager, Combination, Credits,
+    Frequencies, LirsStack, Lookahead, LookaheadModel, NextUse, TwoQueues,
+};
+
+mod hierarchy;
+use hierarchy::{CacheHierarchy, InclusionPolicy};
+
+mod locality;
+use locality::{AccessGraph, MarkovChain};
+
+mod marking;
+use marking::{Chains, Layers, MarkingEviction, MarkingPhases};
+
+mod multicore;
+use multicore::{MulticoreCache, Partitioning, interleave};
 
-mod sampler;
-use sampler::{DistributionType, RandomSampler};
+mod page_list;
+use page_list::PageList;
 
+mod prediction;
+use prediction::{Predictions, Predictor, prediction_error};
+
+mod trace;
+use trace::{TraceReader, TraceWriter};
+
+mod working_set;
+use working_set::{WorkingSets, recovery};
+
+use online_core::results::ResultWriter;
+use online_core::sampler::{DistributionType, RandomSampler};
+use online_core::seed::{derive_seed, master_seed};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{self, Write};
-use std::sync::Mutex;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 
 // Number of simulation trials per combination.
 const TRIALS: usize = 100;
 // Number of page requests per trial.
 const NUM_REQUESTS: usize = 100_000;
+// Header of the results CSV, shared by the synthetic sweep and trace replay.
+const CSV_HEADER: &str = "n;k;cache_strategy;distribution;seed;avg_cost;competitive_ratio";
+// Header of the phase statistics CSV of the marking strategies.
+const PHASE_CSV_HEADER: &str =
+    "n;k;cache_strategy;distribution;seed;phases;new_pages;stale_pages;avg_cost;cost_per_new_page";
+// Number of page requests in a timeline.
+const TIMELINE_REQUESTS: usize = 10_000;
+// Header of the per-access timeline CSV.
+const TIMELINE_CSV_HEADER: &str = "cache_strategy;time;page;hit;evicted;snapshot";
+// Page counts of the resource augmentation sweep.
+const AUGMENTATION_NS: [usize; 2] = [50, 100];
+// Online cache sizes of the resource augmentation sweep; MIN gets every power of two up to k.
+const AUGMENTATION_KS: [usize; 3] = [4, 8, 16];
+// Header of the resource augmentation CSV.
+const AUGMENTATION_CSV_HEADER: &str =
+    "n;k;h;cache_strategy;distribution;seed;avg_cost;opt_avg_cost;ratio;bound";
+// Cache sizes attacked by the adversaries.
+const ADVERSARY_KS: [usize; 4] = [2, 4, 8, 16];
+// Number of page requests per adversary trial.
+const ADVERSARY_REQUESTS: usize = 50_000;
+// Header of the adversary CSV.
+const ADVERSARY_CSV_HEADER: &str =
+    "k;adversary;cache_strategy;seed;avg_cost;opt_avg_cost;ratio;lower_bound";
+// Number of pages of the built-in request models of the locality sweep.
+const LOCALITY_PAGES: usize = 100;
+// Cache sizes of the locality sweep.
+const LOCALITY_KS: [usize; 3] = [5, 10, 20];
+// Header of the locality CSV.
+const LOCALITY_CSV_HEADER: &str = "model;n;k;cache_strategy;seed;avg_cost;competitive_ratio";
+// Cache size of the working-set sweep.
+const WORKING_SET_K: usize = 32;
+// Working set sizes of the working-set sweep, around the cache size.
+const WORKING_SET_SIZES: [usize; 3] = [16, 32, 48];
+// Shares of a working set kept at a shift in the working-set sweep.
+const WORKING_SET_OVERLAPS: [f64; 3] = [0.0, 0.25, 0.5];
+// Range of the number of requests in a phase of the working-set sweep.
+const WORKING_SET_PHASE_LENGTHS: std::ops::RangeInclusive<usize> = 2_000..=4_000;
+// Number of requests the miss rate after a shift is measured over.
+const RECOVERY_WINDOW: usize = 100;
+// Header of the working-set CSV.
+const WORKING_SET_CSV_HEADER: &str =
+    "set_size;overlap;k;cache_strategy;seed;avg_cost;spike;baseline;recovery_time";
+// Number of pages of the hierarchy sweep.
+const HIERARCHY_PAGES: usize = 100;
+// Capacity and latency of each level of the hierarchy sweep, first level first.
+const HIERARCHY_LEVELS: [(usize, f64); 2] = [(8, 1.0), (32, 10.0)];
+// Latency of serving a request from memory in the hierarchy sweep.
+const MEMORY_LATENCY: f64 = 100.0;
+// Header of the hierarchy CSV.
+const HIERARCHY_CSV_HEADER: &str =
+    "distribution;policy;cache_strategy;seed;l1_hit_rate;l2_hit_rate;memory_rate;avg_latency";
+// Number of pages of each stream of the multicore sweep.
+const MULTICORE_PAGES: usize = 50;
+// Total cache sizes of the multicore sweep.
+const MULTICORE_KS: [usize; 3] = [16, 32, 64];
+// Number of requests between two resizes of dynamic partitions in the multicore sweep.
+const MULTICORE_EPOCH: usize = 1_000;
+// Header of the multicore CSV.
+const MULTICORE_CSV_HEADER: &str =
+    "k;partitioning;cache_strategy;seed;stream;distribution;capacity;requests;faults;fault_rate";
+// Number of pages and cache sizes in the lookahead sweep.
+const LOOKAHEAD_PAGES: usize = 100;
+const LOOKAHEAD_KS: [usize; 2] = [10, 20];
+// Lookahead window sizes, from plain LRU upwards.
+const LOOKAHEAD_WINDOWS: [usize; 8] = [0, 1, 2, 4, 8, 16, 32, 64];
+// Greatest number of pages a miss may prefetch in the lookahead sweep (0 disables it).
+const PREFETCH_DEPTHS: [usize; 2] = [0, 4];
+// CSV header for the lookahead results. Prefetches load pages without a fault, so the
+// fault columns leave them out and the fetch columns count them with the faults.
+const LOOKAHEAD_CSV_HEADER: &str = "n;k;model;lookahead;prefetch;distribution;seed;faults_per_request;fetches_per_request;opt_avg_cost;fault_ratio;fetch_ratio";
+// Number of pages and cache sizes in the prediction sweep.
+const PREDICTION_PAGES: usize = 100;
+const PREDICTION_KS: [usize; 2] = [10, 20];
+// Standard deviations, in requests, of the noise added to the predicted next arrivals.
+const PREDICTION_SIGMAS: [f64; 7] = [0.0, 1.0, 10.0, 100.0, 1_000.0, 10_000.0, 100_000.0];
+// CSV header for the prediction results.
+const PREDICTION_CSV_HEADER: &str =
+    "n;k;sigma;cache_strategy;distribution;seed;avg_cost;opt_avg_cost;ratio;error_per_opt";
+// Fetch costs of the storage tiers a page can live on in the weighted sweep.
+const TIER_COSTS: [f64; 3] = [1.0, 10.0, 100.0];
+// Object sizes in the sized sweep are 2^0..=2^MAX_SIZE_EXPONENT bytes, log-uniformly.
+const MAX_SIZE_EXPONENT: u32 = 10;
+const USAGE: &str = "Usage:
+    l2                          Run the synthetic sweep over all distributions
+    l2 weighted                 Run the sweep with a random storage tier cost per page
+    l2 sized                    Run the sweep with a random object size per page
+    l2 phases                   Record the phases of the marking strategies in the sweep
+    l2 augmentation             Run every strategy with k pages against MIN with h <= k pages
+    l2 adversary                Play adaptive and oblivious adversaries against every strategy
+    l2 locality [<edges>]       Run every strategy on Markov chain and access graph requests,
+                                or on random walks on the graph in the given edge list
+    l2 workingset               Measure how every strategy recovers from working set shifts
+    l2 hierarchy                Run every strategy on each level of an inclusive and an
+                                exclusive two-level cache hierarchy
+    l2 multicore                Run every strategy on interleaved streams sharing a cache or
+                                splitting it into static or dynamic partitions
+    l2 lookahead                Run LRU with weak and strong lookahead and prefetching
+                                against MIN
+    l2 predictions              Run the learning-augmented strategies with noisy predictions
+                                next to LRU, RMA and MIN
+    l2 timeline <n> <k> <dist>  Record every access of every strategy on one sweep sequence
+    l2 trace <path> <k>[,<k>..] Replay a page-reference trace for the given cache sizes
+    l2 convert <text> <binary>  Convert a text trace to the binary trace format";
+
+// The distributions the synthetic sweeps draw requests from.
+const DISTRIBUTION_TYPES: [DistributionType; 4] = [
+    DistributionType::Uniform,
+    DistributionType::Harmonic,
+    DistributionType::DoublyHarmonic,
+    DistributionType::Geometric,
+];
+
+/// Produces a fresh strategy, seeding its RNG (if any) with the given seed.
+type StrategyFactory = Box<dyn Fn(u64) -> CacheManagementStrategy + Sync>;
+
+/// Produces a fresh learning-augmented strategy for a cache of the given size from its
+/// predictions, seeding its RNG (if any) with the given seed.
+type PredictiveFactory = fn(usize, Predictions, u64) -> CacheManagementStrategy;
+
+/// The cost model of a synthetic sweep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SweepMode {
+    /// Unit-size pages with unit fetch cost.
+    Paging,
+    /// Unit-size pages, each with the fetch cost of a random storage tier.
+    Weighted,
+    /// Objects of random sizes with unit fetch cost, in a cache measured in bytes.
+    Sized,
+}
 
 fn main() -> io::Result<()> {
     // Start the timer to measure the execution time.
     let start_time = Instant::now();
+    let seed = master_seed();
+    println!("Master seed: {}", seed);
 
-    // Create and open the CSV file for writing.
-    let file = File::create("cache_results.csv")?;
-    let file = Mutex::new(file);
-    writeln!(
-        file.lock().unwrap(),
-        "n;k;cache_strategy;distribution;avg_cost"
-    )?;
-
-    // Define cache strategies as (name, factory function producing a new variant).
-    let cache_strategies: Vec<(&str, Box<dyn Fn() -> CacheManagementStrategy + Sync>)> = vec![
-        ("FIFO", Box::new(|| CacheManagementStrategy::FIFO)),
-        ("FWF", Box::new(|| CacheManagementStrategy::FWF)),
-        (
-            "LRU",
-            Box::new(|| CacheManagementStrategy::LRU(VecDeque::new())),
-        ),
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [] => run_sweep(seed, SweepMode::Paging)?,
+        ["weighted"] => run_sweep(seed, SweepMode::Weighted)?,
+        ["sized"] => run_sweep(seed, SweepMode::Sized)?,
+        ["phases"] => run_phases(seed)?,
+        ["augmentation"] => run_augmentation(seed)?,
+        ["adversary"] => run_adversary(seed)?,
+        ["locality"] => run_locality(seed, None)?,
+        ["locality", edges] => run_locality(seed, Some(Path::new(edges)))?,
+        ["workingset"] => run_working_sets(seed)?,
+        ["hierarchy"] => run_hierarchy(seed)?,
+        ["multicore"] => run_multicore(seed)?,
+        ["lookahead"] => run_lookahead(seed)?,
+        ["predictions"] => run_predictions(seed)?,
+        ["timeline", n, k, distribution] => run_timeline(
+            seed,
+            parse_number("page count", n)?,
+            parse_number("cache size", k)?,
+            parse_distribution(distribution)?,
+        )?,
+        ["trace", path, ks] => run_trace(seed, Path::new(path), &parse_cache_sizes(ks)?)?,
+        ["convert", input, output] => convert_trace(Path::new(input), Path::new(output))?,
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+
+    let elapsed_time = start_time.elapsed();
+    println!(
+        "Total processing time: {:.2?} seconds",
+        elapsed_time.as_secs_f64()
+    );
+
+    Ok(())
+}
+
+/// Returns every cache strategy as (name, factory function producing a new variant from a seed).
+fn cache_strategies() -> Vec<(&'static str, StrategyFactory)> {
+    vec![
+        ("FIFO", Box::new(|_| CacheManagementStrategy::FIFO)),
+        ("FWF", Box::new(|_| CacheManagementStrategy::FWF)),
+        ("LRU", Box::new(|_| CacheManagementStrategy::LRU)),
         (
             "LFU",
-            Box::new(|| CacheManagementStrategy::LFU(HashMap::new())),
+            Box::new(|_| CacheManagementStrategy::LFU(Frequencies::new())),
         ),
         (
             "RAND",
-            Box::new(|| CacheManagementStrategy::RAND(rand::rng())),
+            Box::new(|seed| CacheManagementStrategy::RAND(StdRng::seed_from_u64(seed))),
         ),
         (
             "RMA",
-            Box::new(|| CacheManagementStrategy::RMA(HashMap::new(), rand::rng())),
+            Box::new(|seed| marking(MarkingEviction::Random(StdRng::seed_from_u64(seed)))),
         ),
-    ];
+        (
+            "CLOCK",
+            Box::new(|_| CacheManagementStrategy::CLOCK(HashSet::new())),
+        ),
+        (
+            "2Q",
+            Box::new(|_| CacheManagementStrategy::TwoQ(TwoQueues::new())),
+        ),
+        (
+            "ARC",
+            Box::new(|_| CacheManagementStrategy::ARC(AdaptiveLists::new())),
+        ),
+        (
+            "LIRS",
+            Box::new(|_| CacheManagementStrategy::LIRS(LirsStack::new())),
+        ),
+        ("MARK-FIFO", Box::new(|_| marking(MarkingEviction::FIFO))),
+        (
+            "LAYERED",
+            Box::new(|seed| {
+                marking(MarkingEviction::Layered(
+                    Layers::new(),
+                    StdRng::seed_from_u64(seed),
+                ))
+            }),
+        ),
+    ]
+}
+
+/// Returns the marking strategies, one per eviction policy, for the phase statistics.
+fn marking_strategies() -> Vec<(&'static str, StrategyFactory)> {
+    let mut strategies: Vec<(&'static str, StrategyFactory)> = cache_strategies()
+        .into_iter()
+        .filter(|(name, _)| matches!(*name, "RMA" | "MARK-FIFO" | "LAYERED"))
+        .collect();
+    strategies.push(("MARK-LRU", Box::new(|_| marking(MarkingEviction::LRU))));
+    strategies
+}
+
+/// Returns the learning-augmented strategies, which follow predicted next arrivals.
+fn predictive_strategies() -> Vec<(&'static str, PredictiveFactory)> {
+    vec![
+        ("FTP", |_, predictions, _| {
+            CacheManagementStrategy::FollowPrediction(predictions)
+        }),
+        ("PREDICTIVE-MARKER", |_, predictions, seed| {
+            marking(MarkingEviction::Predictive(
+                Chains::new(predictions),
+                StdRng::seed_from_u64(seed),
+            ))
+        }),
+        ("LMARKER", |_, predictions, seed| {
+            marking(MarkingEviction::LMarker(
+                predictions,
+                StdRng::seed_from_u64(seed),
+            ))
+        }),
+        ("FTP+RMA", |k, predictions, seed| {
+            CacheManagementStrategy::Combiner(Box::new(Combination::new(
+                CacheManager::new(k, CacheManagementStrategy::FollowPrediction(predictions)),
+                CacheManager::new(
+                    k,
+                    marking(MarkingEviction::Random(StdRng::seed_from_u64(seed))),
+                ),
+            )))
+        }),
+    ]
+}
+
+/// Creates a marking strategy with the given eviction policy.
+fn marking(eviction: MarkingEviction) -> CacheManagementStrategy {
+    CacheManagementStrategy::Marking(MarkingPhases::new(eviction))
+}
+
+/// Returns every strategy, including the cost-aware ones, for the weighted sweep.
+fn weighted_cache_strategies() -> Vec<(&'static str, StrategyFactory)> {
+    let mut strategies = cache_strategies();
+    strategies.push((
+        "GreedyDual",
+        Box::new(|_| CacheManagementStrategy::GreedyDual(Credits::new())),
+    ));
+    strategies.push((
+        "WRMA",
+        Box::new(|seed| {
+            CacheManagementStrategy::WRMA(
+                PageList::new(),
+                HashMap::new(),
+                StdRng::seed_from_u64(seed),
+            )
+        }),
+    ));
+    strategies
+}
+
+/// Returns the size-aware strategies for the sized sweep.
+fn sized_cache_strategies() -> Vec<(&'static str, StrategyFactory)> {
+    vec![
+        ("SizeLRU", Box::new(|_| CacheManagementStrategy::SizeLRU)),
+        (
+            "GDS",
+            Box::new(|_| CacheManagementStrategy::Landlord(Credits::new(), 1.0)),
+        ),
+        (
+            "GDSF",
+            Box::new(|_| CacheManagementStrategy::GDSF(Credits::new(), HashMap::new())),
+        ),
+        (
+            "Landlord",
+            Box::new(|_| CacheManagementStrategy::Landlord(Credits::new(), 0.5)),
+        ),
+    ]
+}
+
+/// Creates the results CSV and writes its header.
+fn create_results_file() -> io::Result<ResultWriter> {
+    ResultWriter::create("cache_results.csv", CSV_HEADER)
+}
+
+/// Runs every strategy against synthetic request sequences drawn from each distribution.
+///
+/// Every trial draws one request sequence that all strategies serve, so each online cost can
+/// be divided by the cost of Belady's MIN on the same sequence. In the weighted sweep every
+/// page is put on a random storage tier whose cost it pays on each miss. In the sized sweep
+/// every object gets a random size and the cache holds `k` times the mean object size. MIN is
+/// only optimal for unit paging, so in the other sweeps it is skipped and the competitive
+/// ratio is reported as NaN.
+fn run_sweep(seed: u64, mode: SweepMode) -> io::Result<()> {
+    let cache_strategies = match mode {
+        SweepMode::Paging => cache_strategies(),
+        SweepMode::Weighted => weighted_cache_strategies(),
+        SweepMode::Sized => sized_cache_strategies(),
+    };
+
+    run_tasks(
+        create_results_file()?,
+        sweep_tasks(),
+        |(n, k, distribution_index), payload| {
+            let distribution_type = DISTRIBUTION_TYPES[distribution_index];
+            // Run TRIALS simulation trials.
+            println!(
+                "Running simulation for n={}, k={}, distribution={:?}",
+                n, k, distribution_type
+            );
+            for trial in 0..TRIALS {
+                // Each row can be regenerated from its seed alone.
+                let row_seed = derive_seed(
+                    seed,
+                    &[n as u64, k as u64, distribution_index as u64, trial as u64],
+                );
+                // Draw the shared request sequence from 1..=n.
+                let mut sampler =
+                    RandomSampler::new(distribution_type, n, derive_seed(row_seed, &[0]));
+                let requests = draw_requests(&mut sampler, NUM_REQUESTS);
+                // Page p costs weights[p] on a miss and occupies sizes[p] bytes.
+                let mut page_rng = StdRng::seed_from_u64(derive_seed(row_seed, &[2]));
+                let (weights, sizes): (Vec<f64>, Vec<usize>) = (0..=n)
+                    .map(|_| match mode {
+                        SweepMode::Paging => (1.0, 1),
+                        SweepMode::Weighted => {
+                            (TIER_COSTS[page_rng.random_range(0..TIER_COSTS.len())], 1)
+                        }
+                        SweepMode::Sized => {
+                            (1.0, 1 << page_rng.random_range(0..=MAX_SIZE_EXPONENT))
+                        }
+                    })
+                    .unzip();
+                let capacity = k * sizes[1..].iter().sum::<usize>() / n;
+                let opt_cost = if mode != SweepMode::Paging {
+                    f64::NAN
+                } else {
+                    let mut cache =
+                        CacheManager::new(k, CacheManagementStrategy::MIN(NextUse::new(&requests)));
+                    let opt_cost = serve(&mut cache, &requests, &weights, &sizes);
+                    payload.push_str(&format!(
+                        "{};{};MIN;{:?};{};{};{}\n",
+                        n,
+                        k,
+                        distribution_type,
+                        row_seed,
+                        opt_cost / NUM_REQUESTS as f64,
+                        1.0
+                    ));
+                    opt_cost
+                };
+                for (strategy_index, (strategy_name, strategy_factory)) in
+                    cache_strategies.iter().enumerate()
+                {
+                    // Create a fresh cache manager with capacity k.
+                    let strategy =
+                        strategy_factory(derive_seed(row_seed, &[1, strategy_index as u64]));
+                    let total_cost = serve(
+                        &mut CacheManager::new(capacity, strategy),
+                        &requests,
+                        &weights,
+                        &sizes,
+                    );
+                    payload.push_str(&format!(
+                        "{};{};{};{:?};{};{};{}\n",
+                        n,
+                        k,
+                        strategy_name,
+                        distribution_type,
+                        row_seed,
+                        total_cost / NUM_REQUESTS as f64,
+                        total_cost / opt_cost
+                    ));
+                }
+            }
+        },
+    )
+}
+
+/// Runs the marking strategies against the synthetic sweep's request sequences and records
+/// the number of phases and of new and stale pages of each, next to the cost per new page.
+///
+/// Randomized marking pays at most H(k) per new page in expectation, while the offline
+/// optimum pays at least half a miss per new page.
+fn run_phases(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("phase_results.csv", PHASE_CSV_HEADER)?;
+    let strategies = marking_strategies();
 
-    // Define the distribution types.
-    let distribution_types = [
-        DistributionType::Uniform,
-        DistributionType::Harmonic,
-        DistributionType::DoublyHarmonic,
-        DistributionType::Geometric,
+    run_tasks(
+        file,
+        sweep_tasks(),
+        |(n, k, distribution_index), payload| {
+            let distribution_type = DISTRIBUTION_TYPES[distribution_index];
+            println!(
+                "Recording phases for n={}, k={}, distribution={:?}",
+                n, k, distribution_type
+            );
+            for trial in 0..TRIALS {
+                // Same sequences as the paging sweep.
+                let row_seed = derive_seed(
+                    seed,
+                    &[n as u64, k as u64, distribution_index as u64, trial as u64],
+                );
+                let mut sampler =
+                    RandomSampler::new(distribution_type, n, derive_seed(row_seed, &[0]));
+                let requests = draw_requests(&mut sampler, NUM_REQUESTS);
+                for (strategy_index, (strategy_name, strategy_factory)) in
+                    strategies.iter().enumerate()
+                {
+                    let strategy =
+                        strategy_factory(derive_seed(row_seed, &[3, strategy_index as u64]));
+                    let mut cache = CacheManager::new(k, strategy);
+                    let total_cost: usize = requests.iter().map(|&p| cache.access(p)).sum();
+                    let phases = cache.phases().expect("not a marking strategy");
+                    let new_pages: usize = phases.iter().map(|phase| phase.new_pages).sum();
+                    let stale_pages: usize = phases.iter().map(|phase| phase.stale_pages).sum();
+                    payload.push_str(&format!(
+                        "{};{};{};{:?};{};{};{};{};{};{}\n",
+                        n,
+                        k,
+                        strategy_name,
+                        distribution_type,
+                        row_seed,
+                        phases.len(),
+                        new_pages,
+                        stale_pages,
+                        total_cost as f64 / NUM_REQUESTS as f64,
+                        total_cost as f64 / new_pages as f64
+                    ));
+                }
+            }
+        },
+    )
+}
+
+/// Runs every strategy with a cache of k pages against Belady's MIN with a cache of h <= k
+/// pages on the same sequences, next to the Sleator-Tarjan bound k / (k - h + 1) on the
+/// competitive ratio of FIFO and LRU (and of every deterministic marking algorithm).
+///
+/// Every sequence is served with each k in `AUGMENTATION_KS` and each power of two h <= k.
+fn run_augmentation(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("augmentation_results.csv", AUGMENTATION_CSV_HEADER)?;
+    let cache_strategies = cache_strategies();
+    let max_k = AUGMENTATION_KS.into_iter().max().unwrap();
+
+    // One sequence per trial is served with every (k, h).
+    run_trials(
+        seed,
+        file,
+        "augmentation",
+        &AUGMENTATION_NS,
+        seeds_per_page_count,
+        |trial, payload| {
+            let opt_costs: Vec<(usize, usize)> = (0..)
+                .map(|exponent| 1 << exponent)
+                .take_while(|&h| h <= max_k)
+                .map(|h| (h, trial.opt_cost(h)))
+                .collect();
+            for k in AUGMENTATION_KS {
+                for (strategy_index, (strategy_name, strategy_factory)) in
+                    cache_strategies.iter().enumerate()
+                {
+                    let strategy = strategy_factory(derive_seed(
+                        trial.seed,
+                        &[1, k as u64, strategy_index as u64],
+                    ));
+                    let total_cost = trial.cost(k, strategy);
+                    for &(h, opt_cost) in opt_costs.iter().filter(|&&(h, _)| h <= k) {
+                        payload.push_str(&format!(
+                            "{};{};{};{};{:?};{};{};{};{};{}\n",
+                            trial.pages,
+                            k,
+                            h,
+                            strategy_name,
+                            trial.distribution_type,
+                            trial.seed,
+                            total_cost as f64 / NUM_REQUESTS as f64,
+                            opt_cost as f64 / NUM_REQUESTS as f64,
+                            total_cost as f64 / opt_cost as f64,
+                            k as f64 / (k - h + 1) as f64
+                        ));
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Plays each adversary against every strategy and compares the cost to MIN's on the same
+/// requests, next to the lower bound the adversary is built to reproduce: `k` for the
+/// adaptive adversary and H(k) for the oblivious ones. Both sides pay `k` compulsory misses,
+/// which pull the ratios below the bounds on short sequences.
+fn run_adversary(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("adversary_results.csv", ADVERSARY_CSV_HEADER)?;
+    let cache_strategies = cache_strategies();
+    type AdversaryFactory = fn(usize, u64) -> Adversary;
+    let adversaries: [(&str, AdversaryFactory); 3] = [
+        ("Adaptive", |k, _| Adversary::adaptive(k)),
+        ("Uniform", Adversary::uniform),
+        ("Phases", Adversary::phases),
     ];
 
-    // n is the endpoint (sample pages from 1..=n)
-    // k is the cache (page) size.
-    (20..=100)
-        .step_by(10)
-        .collect::<Vec<_>>()
-        .into_par_iter()
-        .for_each(|n| {
-            (n / 10..=n / 5)
-                .collect::<Vec<_>>()
-                .into_par_iter()
-                .for_each(|k| {
-                    cache_strategies.par_iter().for_each(
-                        |&(strategy_name, ref strategy_factory)| {
-                            distribution_types.par_iter().for_each(|&distribution_type| {
-                            // Create a RandomSampler for the current distribution, sampling from 1..=n.
-                            let mut sampler = RandomSampler::new(distribution_type, n);
-                            // Run TRIALS simulation trials.
-                            println!(
-                                "Running simulation for n={}, k={}, strategy={}, distribution={:?}",
-                                n, k, strategy_name, distribution_type
-                            );
-                            let mut payload = String::new();
-                            let mut total_cost: usize;
-                            for _ in 0..TRIALS {
-                                total_cost = 0;
-                                // Create a fresh cache manager with capacity k.
-                                let mut cache = CacheManager::new(k, strategy_factory());
-                                // Simulate NUM_REQUESTS page accesses.
-                                for _ in 0..NUM_REQUESTS {
-                                    let page = sampler.sample() as usize;
-                                    total_cost += cache.access(page);
-                                }
-                                payload.push_str(&format!(
-                                    "{};{};{};{:?};{}\n",
-                                    n, k, strategy_name, distribution_type, total_cost as f64 / NUM_REQUESTS as f64
-                                ));
-                            }
-                            let mut file = file.lock().unwrap();
-                            write!(file, "{}", payload).expect("Failed to write to file");
-                        });
+    let tasks: Vec<(usize, usize)> = ADVERSARY_KS
+        .into_iter()
+        .flat_map(|k| (0..TRIALS).map(move |trial| (k, trial)))
+        .collect();
+    run_tasks(file, tasks, |(k, trial), payload| {
+        if trial == 0 {
+            println!("Running adversaries for k={}", k);
+        }
+        let harmonic: f64 = (1..=k).map(|i| 1.0 / i as f64).sum();
+        for (adversary_index, (adversary_name, adversary_factory)) in adversaries.iter().enumerate()
+        {
+            let lower_bound = if adversary_index == 0 {
+                k as f64
+            } else {
+                harmonic
+            };
+            for (strategy_index, (strategy_name, strategy_factory)) in
+                cache_strategies.iter().enumerate()
+            {
+                // Each row can be regenerated from its seed alone.
+                let row_seed = derive_seed(
+                    seed,
+                    &[
+                        k as u64,
+                        adversary_index as u64,
+                        strategy_index as u64,
+                        trial as u64,
+                    ],
+                );
+                let mut cache = CacheManager::new(k, strategy_factory(derive_seed(row_seed, &[1])));
+                let mut adversary = adversary_factory(k, derive_seed(row_seed, &[0]));
+                let (requests, total_cost) = adversary.play(&mut cache, ADVERSARY_REQUESTS);
+                let mut opt =
+                    CacheManager::new(k, CacheManagementStrategy::MIN(NextUse::new(&requests)));
+                let opt_cost: usize = requests.iter().map(|&page| opt.access(page)).sum();
+                payload.push_str(&format!(
+                    "{};{};{};{};{};{};{};{}\n",
+                    k,
+                    adversary_name,
+                    strategy_name,
+                    row_seed,
+                    total_cost as f64 / ADVERSARY_REQUESTS as f64,
+                    opt_cost as f64 / ADVERSARY_REQUESTS as f64,
+                    total_cost as f64 / opt_cost as f64,
+                    lower_bound
+                ));
+            }
+        }
+    })
+}
+
+/// Runs every strategy against request sequences with locality of reference and compares
+/// the cost to MIN's on the same sequences.
+///
+/// The built-in models are random walks on a path, a cycle and a binary tree over
+/// `LOCALITY_PAGES` pages, and a Markov chain jumping to nearby pages on a cycle with
+/// doubly harmonic weights. Given an edge list, random walks on that graph are run instead.
+/// On the access graph models FAR marking, which knows the graph, runs next to the others.
+fn run_locality(seed: u64, edges: Option<&Path>) -> io::Result<()> {
+    let models: Vec<(String, Option<Arc<AccessGraph>>)> = match edges {
+        None => vec![
+            (
+                "Path".to_string(),
+                Some(Arc::new(AccessGraph::path(LOCALITY_PAGES))),
+            ),
+            (
+                "Cycle".to_string(),
+                Some(Arc::new(AccessGraph::cycle(LOCALITY_PAGES))),
+            ),
+            (
+                "Tree".to_string(),
+                Some(Arc::new(AccessGraph::tree(LOCALITY_PAGES, 2))),
+            ),
+            ("Markov".to_string(), None),
+        ],
+        Some(path) => {
+            let graph = AccessGraph::read_edges(BufReader::new(File::open(path)?))?;
+            if graph.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} holds no edges", path.display()),
+                ));
+            }
+            if let Some(page_id) = (1..=graph.len()).find(|&p| graph.neighbours(p).is_empty()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Page {} has no edge, so a random walk cannot leave it",
+                        page_id
+                    ),
+                ));
+            }
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            vec![(name, Some(Arc::new(graph)))]
+        }
+    };
+
+    let file = ResultWriter::create("locality_results.csv", LOCALITY_CSV_HEADER)?;
+    let tasks: Vec<(usize, usize)> = (0..models.len())
+        .flat_map(|model_index| LOCALITY_KS.into_iter().map(move |k| (model_index, k)))
+        .collect();
+    run_tasks(file, tasks, |(model_index, k), payload| {
+        let (model_name, graph) = &models[model_index];
+        let n = graph.as_ref().map_or(LOCALITY_PAGES, |graph| graph.len());
+        if k >= n {
+            return;
+        }
+        println!("Running locality for model={}, k={}", model_name, k);
+        let mut cache_strategies = cache_strategies();
+        if let Some(graph) = graph {
+            let graph = Arc::clone(graph);
+            cache_strategies.push((
+                "FAR",
+                Box::new(move |_| marking(MarkingEviction::FAR(Arc::clone(&graph)))),
+            ));
+        }
+
+        for trial in 0..TRIALS {
+            // Each row can be regenerated from its seed alone.
+            let row_seed = derive_seed(seed, &[model_index as u64, k as u64, trial as u64]);
+            let chain_seed = derive_seed(row_seed, &[0]);
+            let mut chain = match graph {
+                Some(graph) => MarkovChain::random_walk(graph, chain_seed),
+                None => MarkovChain::distance_decay(n, 2.0, chain_seed),
+            };
+            let requests: Vec<usize> = (0..NUM_REQUESTS).map(|_| chain.sample()).collect();
+            let mut opt =
+                CacheManager::new(k, CacheManagementStrategy::MIN(NextUse::new(&requests)));
+            let opt_cost: usize = requests.iter().map(|&page| opt.access(page)).sum();
+            payload.push_str(&format!(
+                "{};{};{};MIN;{};{};{}\n",
+                model_name,
+                n,
+                k,
+                row_seed,
+                opt_cost as f64 / NUM_REQUESTS as f64,
+                1.0
+            ));
+            for (strategy_index, (strategy_name, strategy_factory)) in
+                cache_strategies.iter().enumerate()
+            {
+                let strategy = strategy_factory(derive_seed(row_seed, &[1, strategy_index as u64]));
+                let mut cache = CacheManager::new(k, strategy);
+                let total_cost: usize = requests.iter().map(|&page| cache.access(page)).sum();
+                payload.push_str(&format!(
+                    "{};{};{};{};{};{};{}\n",
+                    model_name,
+                    n,
+                    k,
+                    strategy_name,
+                    row_seed,
+                    total_cost as f64 / NUM_REQUESTS as f64,
+                    total_cost as f64 / opt_cost as f64
+                ));
+            }
+        }
+    })
+}
+
+/// Runs every strategy, MIN included, on workloads that shift between working sets and
+/// measures the miss spike after each shift and the time to recover from it.
+///
+/// Working sets of each size in `WORKING_SET_SIZES` keep each share in
+/// `WORKING_SET_OVERLAPS` of their pages at a shift, and the cache holds `WORKING_SET_K`
+/// pages, so sets larger than the cache keep missing after they settle.
+fn run_working_sets(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("working_set_results.csv", WORKING_SET_CSV_HEADER)?;
+    let cache_strategies = cache_strategies();
+
+    let tasks: Vec<(usize, usize)> = (0..WORKING_SET_SIZES.len())
+        .flat_map(|size_index| {
+            (0..WORKING_SET_OVERLAPS.len()).map(move |overlap_index| (size_index, overlap_index))
+        })
+        .collect();
+    run_tasks(file, tasks, |(size_index, overlap_index), payload| {
+        let size = WORKING_SET_SIZES[size_index];
+        let overlap = (WORKING_SET_OVERLAPS[overlap_index] * size as f64).round() as usize;
+        println!("Running working sets of size={}, overlap={}", size, overlap);
+        for trial in 0..TRIALS {
+            // Each row can be regenerated from its seed alone.
+            let row_seed = derive_seed(
+                seed,
+                &[size_index as u64, overlap_index as u64, trial as u64],
+            );
+            let mut generator = WorkingSets::new(
+                size,
+                overlap,
+                WORKING_SET_PHASE_LENGTHS,
+                derive_seed(row_seed, &[0]),
+            );
+            let requests: Vec<usize> = (0..NUM_REQUESTS).map(|_| generator.sample()).collect();
+            let shifts = generator.shifts();
+
+            let strategies =
+                std::iter::once(("MIN", CacheManagementStrategy::MIN(NextUse::new(&requests))))
+                    .chain(cache_strategies.iter().enumerate().map(
+                        |(strategy_index, (strategy_name, strategy_factory))| {
+                            (
+                                *strategy_name,
+                                strategy_factory(derive_seed(
+                                    row_seed,
+                                    &[1, strategy_index as u64],
+                                )),
+                            )
                         },
-                    );
+                    ));
+            for (strategy_name, strategy) in strategies {
+                let mut cache = CacheManager::new(WORKING_SET_K, strategy);
+                let costs: Vec<usize> = requests.iter().map(|&page| cache.access(page)).collect();
+                let recovery = recovery(&costs, shifts, RECOVERY_WINDOW);
+                payload.push_str(&format!(
+                    "{};{};{};{};{};{};{};{};{}\n",
+                    size,
+                    overlap,
+                    WORKING_SET_K,
+                    strategy_name,
+                    row_seed,
+                    costs.iter().sum::<usize>() as f64 / NUM_REQUESTS as f64,
+                    recovery.spike,
+                    recovery.baseline,
+                    recovery.recovery_time
+                ));
+            }
+        }
+    })
+}
+
+/// Runs every strategy on both levels of a two-level cache hierarchy, once inclusive and once
+/// exclusive, and records the share of requests each level and memory serve next to the
+/// average latency per request.
+///
+/// Requests are drawn from `HIERARCHY_PAGES` pages with each distribution, and the levels
+/// are configured by `HIERARCHY_LEVELS`.
+fn run_hierarchy(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("hierarchy_results.csv", HIERARCHY_CSV_HEADER)?;
+    let cache_strategies = cache_strategies();
+    let policies = [InclusionPolicy::Inclusive, InclusionPolicy::Exclusive];
+
+    // One sequence per trial is served by every hierarchy.
+    run_trials(
+        seed,
+        file,
+        "hierarchy",
+        &[HIERARCHY_PAGES],
+        seeds_per_distribution,
+        |trial, payload| {
+            for (policy_index, policy) in policies.into_iter().enumerate() {
+                for (strategy_index, (strategy_name, strategy_factory)) in
+                    cache_strategies.iter().enumerate()
+                {
+                    let mut hierarchy = CacheHierarchy::new(policy, MEMORY_LATENCY);
+                    for (level_index, (capacity, latency)) in
+                        HIERARCHY_LEVELS.into_iter().enumerate()
+                    {
+                        let strategy = strategy_factory(derive_seed(
+                            trial.seed,
+                            &[
+                                1,
+                                policy_index as u64,
+                                strategy_index as u64,
+                                level_index as u64,
+                            ],
+                        ));
+                        hierarchy.push_level(CacheManager::new(capacity, strategy), latency);
+                    }
+                    for &page in &trial.requests {
+                        hierarchy.access(page);
+                    }
+                    let hits = hierarchy.hits();
+                    payload.push_str(&format!(
+                        "{:?};{:?};{};{};{};{};{};{}\n",
+                        trial.distribution_type,
+                        policy,
+                        strategy_name,
+                        trial.seed,
+                        hits[0] as f64 / NUM_REQUESTS as f64,
+                        hits[1] as f64 / NUM_REQUESTS as f64,
+                        hits[2] as f64 / NUM_REQUESTS as f64,
+                        hierarchy.total_latency() / NUM_REQUESTS as f64
+                    ));
+                }
+            }
+        },
+    )
+}
+
+/// Runs every strategy on one request stream per distribution, randomly interleaved, with a
+/// shared cache, equal static partitions and dynamic partitions, and records the faults of
+/// each stream to compare how fairly each setup treats the streams.
+///
+/// Each stream requests `NUM_REQUESTS / p` of its own `MULTICORE_PAGES` pages.
+fn run_multicore(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("multicore_results.csv", MULTICORE_CSV_HEADER)?;
+    let cache_strategies = cache_strategies();
+    let streams = DISTRIBUTION_TYPES.len();
+
+    let tasks: Vec<(usize, usize)> = MULTICORE_KS
+        .into_iter()
+        .flat_map(|k| (0..TRIALS).map(move |trial| (k, trial)))
+        .collect();
+    run_tasks(file, tasks, |(k, trial), payload| {
+        if trial == 0 {
+            println!("Running multicore for k={}", k);
+        }
+        // One interleaving per trial is served by every setup.
+        let trial_seed = derive_seed(seed, &[k as u64, trial as u64]);
+        let stream_requests: Vec<Vec<usize>> = DISTRIBUTION_TYPES
+            .iter()
+            .enumerate()
+            .map(|(stream, &distribution_type)| {
+                let mut sampler = RandomSampler::new(
+                    distribution_type,
+                    MULTICORE_PAGES,
+                    derive_seed(trial_seed, &[0, stream as u64]),
+                );
+                draw_requests(&mut sampler, NUM_REQUESTS / streams)
+            })
+            .collect();
+        let requests = interleave(&stream_requests, derive_seed(trial_seed, &[1]));
+        let partitionings = [
+            ("Shared", Partitioning::Shared),
+            ("Static", Partitioning::Static(vec![k / streams; streams])),
+            // LIRS needs a cache of at least two pages.
+            (
+                "Dynamic",
+                Partitioning::Dynamic {
+                    epoch: MULTICORE_EPOCH,
+                    minimum: 2,
+                },
+            ),
+        ];
+
+        for (partitioning_index, (partitioning_name, partitioning)) in
+            partitionings.into_iter().enumerate()
+        {
+            for (strategy_index, (strategy_name, strategy_factory)) in
+                cache_strategies.iter().enumerate()
+            {
+                let mut cache = MulticoreCache::new(streams, k, partitioning.clone(), |build| {
+                    strategy_factory(derive_seed(
+                        trial_seed,
+                        &[
+                            2,
+                            partitioning_index as u64,
+                            strategy_index as u64,
+                            build as u64,
+                        ],
+                    ))
                 });
-        });
-    let elapsed_time = start_time.elapsed();
-    println!(
-        "Total processing time: {:.2?} seconds",
-        elapsed_time.as_secs_f64()
+                for &(stream, page) in &requests {
+                    cache.access(stream, page);
+                }
+                for (stream, distribution_type) in DISTRIBUTION_TYPES.iter().enumerate() {
+                    let (stream_requests, faults) =
+                        (cache.requests()[stream], cache.faults()[stream]);
+                    // A shared cache has a single capacity, the total.
+                    let capacities = cache.capacities();
+                    payload.push_str(&format!(
+                        "{};{};{};{};{};{:?};{};{};{};{}\n",
+                        k,
+                        partitioning_name,
+                        strategy_name,
+                        trial_seed,
+                        stream,
+                        distribution_type,
+                        capacities[stream.min(capacities.len() - 1)],
+                        stream_requests,
+                        faults,
+                        faults as f64 / stream_requests as f64
+                    ));
+                }
+            }
+        }
+    })
+}
+
+/// Runs LRU with every lookahead window under the weak and the strong model, with and
+/// without prefetching, and compares its cost to MIN's on the same requests.
+///
+/// Prefetches are free in `faults_per_request` and `fault_ratio`, which count demand misses
+/// only, and are counted along with the misses in `fetches_per_request` and `fetch_ratio`.
+/// MIN never prefetches, so `fetch_ratio` compares the pages both load.
+fn run_lookahead(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("lookahead_results.csv", LOOKAHEAD_CSV_HEADER)?;
+    let models = [LookaheadModel::Weak, LookaheadModel::Strong];
+
+    // One sequence per trial is served with every setup.
+    run_trials(
+        seed,
+        file,
+        "lookahead",
+        &[LOOKAHEAD_PAGES],
+        |seed, _, distribution_index, trial| {
+            // The lookahead requests are drawn from the trial seed itself.
+            let trial_seed = derive_seed(seed, &[distribution_index as u64, trial as u64]);
+            (trial_seed, trial_seed)
+        },
+        |trial, payload| {
+            let requests = &trial.requests;
+            for k in LOOKAHEAD_KS {
+                let opt_cost = trial.opt_cost(k);
+                for model in models {
+                    for window in LOOKAHEAD_WINDOWS {
+                        for depth in PREFETCH_DEPTHS {
+                            let strategy = CacheManagementStrategy::Lookahead(Lookahead::new(
+                                requests, window, model, depth,
+                            ));
+                            let mut cache = CacheManager::new(k, strategy);
+                            let total_cost: usize =
+                                requests.iter().map(|&page| cache.access(page)).sum();
+                            let fetches = total_cost + cache.prefetches().unwrap();
+                            payload.push_str(&format!(
+                                "{};{};{:?};{};{};{:?};{};{};{};{};{};{}\n",
+                                LOOKAHEAD_PAGES,
+                                k,
+                                model,
+                                window,
+                                depth,
+                                trial.distribution_type,
+                                trial.seed,
+                                total_cost as f64 / NUM_REQUESTS as f64,
+                                fetches as f64 / NUM_REQUESTS as f64,
+                                opt_cost as f64 / NUM_REQUESTS as f64,
+                                total_cost as f64 / opt_cost as f64,
+                                fetches as f64 / opt_cost as f64
+                            ));
+                        }
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Runs the learning-augmented strategies with predictions of every noise level, next to LRU
+/// and RMA, which ignore them, and compares their cost to MIN's on the same requests.
+///
+/// The prediction error is reported as η / OPT, the total distance between predicted and
+/// actual next arrivals over MIN's cost, which the bounds of the learning-augmented
+/// strategies are stated in.
+fn run_predictions(seed: u64) -> io::Result<()> {
+    let file = ResultWriter::create("prediction_results.csv", PREDICTION_CSV_HEADER)?;
+    let baselines: Vec<(&'static str, StrategyFactory)> = cache_strategies()
+        .into_iter()
+        .filter(|(name, _)| matches!(*name, "LRU" | "RMA"))
+        .collect();
+    let predictive_strategies = predictive_strategies();
+
+    // One sequence per trial is served with every noise level.
+    run_trials(
+        seed,
+        file,
+        "predictions",
+        &[PREDICTION_PAGES],
+        seeds_per_distribution,
+        |trial, payload| {
+            let predictions: Vec<(f64, Predictions)> = PREDICTION_SIGMAS
+                .iter()
+                .enumerate()
+                .map(|(sigma_index, &sigma)| {
+                    let predictor = if sigma == 0.0 {
+                        Predictor::Oracle
+                    } else {
+                        let rng = StdRng::seed_from_u64(derive_seed(
+                            trial.seed,
+                            &[1, sigma_index as u64],
+                        ));
+                        Predictor::Noisy(sigma, rng)
+                    };
+                    (sigma, predictor.predict(&trial.requests))
+                })
+                .collect();
+            let errors: Vec<usize> = predictions
+                .iter()
+                .map(|(_, predictions)| prediction_error(&trial.requests, predictions))
+                .collect();
+
+            for k in PREDICTION_KS {
+                let opt_cost = trial.opt_cost(k);
+                let baseline_costs: Vec<(&str, usize)> = baselines
+                    .iter()
+                    .enumerate()
+                    .map(|(strategy_index, (strategy_name, strategy_factory))| {
+                        let strategy = strategy_factory(derive_seed(
+                            trial.seed,
+                            &[2, k as u64, strategy_index as u64],
+                        ));
+                        (*strategy_name, trial.cost(k, strategy))
+                    })
+                    .collect();
+                for (sigma_index, (sigma, predictions)) in predictions.iter().enumerate() {
+                    let mut costs = baseline_costs.clone();
+                    for (strategy_index, (strategy_name, strategy_factory)) in
+                        predictive_strategies.iter().enumerate()
+                    {
+                        let strategy = strategy_factory(
+                            k,
+                            predictions.clone(),
+                            derive_seed(
+                                trial.seed,
+                                &[3, k as u64, sigma_index as u64, strategy_index as u64],
+                            ),
+                        );
+                        costs.push((strategy_name, trial.cost(k, strategy)));
+                    }
+                    for (strategy_name, total_cost) in costs {
+                        payload.push_str(&format!(
+                            "{};{};{};{};{:?};{};{};{};{};{}\n",
+                            PREDICTION_PAGES,
+                            k,
+                            sigma,
+                            strategy_name,
+                            trial.distribution_type,
+                            trial.seed,
+                            total_cost as f64 / NUM_REQUESTS as f64,
+                            opt_cost as f64 / NUM_REQUESTS as f64,
+                            total_cost as f64 / opt_cost as f64,
+                            errors[sigma_index] as f64 / opt_cost as f64
+                        ));
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Returns the (n, k) pairs of the synthetic sweeps: pages are drawn from 1..=n and the
+/// cache holds k of them.
+fn sweep_sizes() -> Vec<(usize, usize)> {
+    (20..=100)
+        .step_by(10)
+        .flat_map(|n| (n / 10..=n / 5).map(move |k| (n, k)))
+        .collect()
+}
+
+/// Draws a request sequence of `len` pages.
+fn draw_requests(sampler: &mut RandomSampler, len: usize) -> Vec<usize> {
+    let mut requests = vec![0; len];
+    sampler.sample_into(&mut requests);
+    requests.into_iter().map(|page| page as usize).collect()
+}
+
+/// Returns the (n, k, distribution index) tasks of the synthetic sweeps.
+fn sweep_tasks() -> Vec<(usize, usize, usize)> {
+    sweep_sizes()
+        .into_iter()
+        .flat_map(|(n, k)| {
+            (0..DISTRIBUTION_TYPES.len()).map(move |distribution_index| (n, k, distribution_index))
+        })
+        .collect()
+}
+
+/// One trial of a sweep: a request sequence drawn from one distribution, which every setup
+/// of the sweep serves.
+struct Trial {
+    /// The number of pages the requests are drawn from.
+    pages: usize,
+    /// The distribution the requests are drawn from.
+    distribution_type: DistributionType,
+    /// The seed every random choice of the trial derives from.
+    seed: u64,
+    /// The requests of the trial.
+    requests: Vec<usize>,
+}
+
+impl Trial {
+    /// Returns the number of misses of a cache of `k` pages serving the requests with the
+    /// given strategy.
+    fn cost(&self, k: usize, strategy: CacheManagementStrategy) -> usize {
+        let mut cache = CacheManager::new(k, strategy);
+        self.requests.iter().map(|&page| cache.access(page)).sum()
+    }
+
+    /// Returns the number of misses of Belady's MIN with a cache of `k` pages.
+    fn opt_cost(&self, k: usize) -> usize {
+        self.cost(
+            k,
+            CacheManagementStrategy::MIN(NextUse::new(&self.requests)),
+        )
+    }
+}
+
+/// Runs the tasks of a sweep in parallel and writes the rows of each task to the results
+/// file as one payload.
+///
+/// # Arguments
+///
+/// * `file` - The results file, with its header written.
+/// * `tasks` - The tasks of the sweep.
+/// * `rows` - Runs a task, appending its rows to the payload.
+fn run_tasks<T: Send>(
+    file: ResultWriter,
+    tasks: Vec<T>,
+    rows: impl Fn(T, &mut String) + Sync,
+) -> io::Result<()> {
+    tasks.into_par_iter().for_each(|task| {
+        let mut payload = String::new();
+        rows(task, &mut payload);
+        file.write(&payload).expect("Failed to write to file");
+    });
+    file.finish()
+}
+
+/// Runs `TRIALS` trials of `NUM_REQUESTS` requests for each page count and distribution in
+/// parallel, and writes the rows of each trial to the results file.
+///
+/// # Arguments
+///
+/// * `seed` - The master seed.
+/// * `file` - The results file, with its header written.
+/// * `label` - The name of the sweep, printed as the first trial of each setup starts.
+/// * `pages` - The page counts the requests are drawn from.
+/// * `seeds` - Derives the seeds of each trial.
+/// * `rows` - Serves a trial with every setup of the sweep, appending the rows to the payload.
+fn run_trials(
+    seed: u64,
+    file: ResultWriter,
+    label: &str,
+    pages: &[usize],
+    seeds: TrialSeeds,
+    rows: impl Fn(&Trial, &mut String) + Sync,
+) -> io::Result<()> {
+    let tasks: Vec<(usize, usize, usize)> = pages
+        .iter()
+        .flat_map(|&n| {
+            (0..DISTRIBUTION_TYPES.len()).flat_map(move |distribution_index| {
+                (0..TRIALS).map(move |trial| (n, distribution_index, trial))
+            })
+        })
+        .collect();
+    run_tasks(file, tasks, |(n, distribution_index, trial), payload| {
+        let distribution_type = DISTRIBUTION_TYPES[distribution_index];
+        if trial == 0 {
+            println!(
+                "Running {} for n={}, distribution={:?}",
+                label, n, distribution_type
+            );
+        }
+        // Each trial can be regenerated from its seed alone.
+        let (trial_seed, sampler_seed) = seeds(seed, n, distribution_index, trial);
+        let mut sampler = RandomSampler::new(distribution_type, n, sampler_seed);
+        let trial = Trial {
+            pages: n,
+            distribution_type,
+            seed: trial_seed,
+            requests: draw_requests(&mut sampler, NUM_REQUESTS),
+        };
+        rows(&trial, payload);
+    })
+}
+
+/// Derives the seed of a trial and the seed its requests are drawn with from the master seed
+/// and the page count, distribution index and number of the trial.
+type TrialSeeds = fn(u64, usize, usize, usize) -> (u64, u64);
+
+/// Derives the trial seed from the page count, distribution and trial, and draws the requests
+/// from its index 0.
+fn seeds_per_page_count(
+    seed: u64,
+    n: usize,
+    distribution_index: usize,
+    trial: usize,
+) -> (u64, u64) {
+    let trial_seed = derive_seed(seed, &[n as u64, distribution_index as u64, trial as u64]);
+    (trial_seed, derive_seed(trial_seed, &[0]))
+}
+
+/// Derives the trial seed from the distribution and trial alone, for sweeps over a single
+/// page count, and draws the requests from its index 0.
+fn seeds_per_distribution(
+    seed: u64,
+    _: usize,
+    distribution_index: usize,
+    trial: usize,
+) -> (u64, u64) {
+    let trial_seed = derive_seed(seed, &[distribution_index as u64, trial as u64]);
+    (trial_seed, derive_seed(trial_seed, &[0]))
+}
+
+/// Serves every request of the sequence, page `p` costing `weights[p]` and occupying
+/// `sizes[p]`, and returns the total cost.
+fn serve(cache: &mut CacheManager, requests: &[usize], weights: &[f64], sizes: &[usize]) -> f64 {
+    requests
+        .iter()
+        .map(|&page| cache.access_sized(page, sizes[page], weights[page]))
+        .sum()
+}
+
+/// Parses a comma-separated list of cache sizes, e.g. `16,32,64`.
+fn parse_cache_sizes(ks: &str) -> io::Result<Vec<usize>> {
+    ks.split(',')
+        .map(|k| parse_number("cache size", k))
+        .collect()
+}
+
+/// Parses a non-negative integer argument, naming it in the error message.
+fn parse_number(what: &str, value: &str) -> io::Result<usize> {
+    value.trim().parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid {} {:?}", what, value),
+        )
+    })
+}
+
+/// Parses a distribution name, ignoring case, into its index in `DISTRIBUTION_TYPES`.
+fn parse_distribution(name: &str) -> io::Result<usize> {
+    DISTRIBUTION_TYPES
+        .iter()
+        .position(|distribution| format!("{:?}", distribution).eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unknown distribution {:?}", name),
+            )
+        })
+}
+
+/// Records every access of every strategy, MIN included, on the first `TIMELINE_REQUESTS`
+/// requests of the paging sweep's first trial for the given n, k and distribution.
+///
+/// The events go to `timeline.csv`, from which miss rates over time can be plotted. The
+/// first request after which each strategy's cache differs from MIN's is printed; any two
+/// strategies can be compared the same way through the `snapshot` column.
+fn run_timeline(seed: u64, n: usize, k: usize, distribution_index: usize) -> io::Result<()> {
+    let distribution_type = DISTRIBUTION_TYPES[distribution_index];
+    let row_seed = derive_seed(seed, &[n as u64, k as u64, distribution_index as u64, 0]);
+    let mut sampler = RandomSampler::new(distribution_type, n, derive_seed(row_seed, &[0]));
+    let requests = draw_requests(&mut sampler, TIMELINE_REQUESTS);
+
+    let mut strategies = vec![("MIN", CacheManagementStrategy::MIN(NextUse::new(&requests)))];
+    for (strategy_index, (strategy_name, strategy_factory)) in cache_strategies().iter().enumerate()
+    {
+        let strategy = strategy_factory(derive_seed(row_seed, &[1, strategy_index as u64]));
+        strategies.push((strategy_name, strategy));
+    }
+
+    let file = ResultWriter::create("timeline.csv", TIMELINE_CSV_HEADER)?;
+    let mut min_snapshots = Vec::new();
+    for (strategy_name, strategy) in strategies {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut cache = CacheManager::new(k, strategy);
+        cache.set_observer(move |event: &AccessEvent| sender.send(event.clone()).unwrap());
+        for &page in &requests {
+            cache.access(page);
+        }
+        drop(cache);
+
+        let mut snapshots = Vec::with_capacity(requests.len());
+        let mut rows = Vec::with_capacity(requests.len());
+        for event in receiver {
+            let evicted: Vec<String> = event.evicted.iter().map(usize::to_string).collect();
+            rows.push(format!(
+                "{};{};{};{};{};{}",
+                strategy_name,
+                event.time,
+                event.page_id,
+                event.hit,
+                evicted.join(","),
+                event.snapshot
+            ));
+            snapshots.push(event.snapshot);
+        }
+        file.write_rows(rows)?;
+        if strategy_name == "MIN" {
+            min_snapshots = snapshots;
+        } else {
+            match snapshots
+                .iter()
+                .zip(&min_snapshots)
+                .position(|(a, b)| a != b)
+            {
+                Some(time) => println!(
+                    "{}: cache first differs from MIN's at request {}",
+                    strategy_name, time
+                ),
+                None => println!("{}: cache never differs from MIN's", strategy_name),
+            }
+        }
+    }
+
+    file.finish()
+}
+
+/// Replays the trace at `path` through every strategy for each cache size in `ks`.
+///
+/// The trace is streamed from disk once per (k, strategy) pair, so it never has to fit in
+/// memory. Rows use the sweep's CSV schema, with `n` the number of distinct pages and
+/// `distribution` the file name of the trace. MIN needs the whole sequence up front, so
+/// the competitive ratio is reported as NaN.
+fn run_trace(seed: u64, path: &Path, ks: &[usize]) -> io::Result<()> {
+    // A first pass validates the trace and counts the distinct pages.
+    let mut pages = HashSet::new();
+    for page in TraceReader::open(path)? {
+        pages.insert(page?);
+    }
+    let n = pages.len();
+    drop(pages);
+
+    let file = create_results_file()?;
+    let cache_strategies = cache_strategies();
+    let trace_name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
     );
 
+    let jobs: Vec<(usize, usize)> = ks
+        .iter()
+        .flat_map(|&k| (0..cache_strategies.len()).map(move |strategy_index| (k, strategy_index)))
+        .collect();
+    jobs.into_par_iter()
+        .try_for_each(|(k, strategy_index)| -> io::Result<()> {
+            let (strategy_name, strategy_factory) = &cache_strategies[strategy_index];
+            println!(
+                "Replaying {} for k={}, strategy={}",
+                trace_name, k, strategy_name
+            );
+            // Each row can be regenerated from its seed alone.
+            let row_seed = derive_seed(seed, &[k as u64, strategy_index as u64]);
+            let mut cache = CacheManager::new(k, strategy_factory(row_seed));
+            let mut total_cost = 0;
+            let mut requests = 0;
+            for page in TraceReader::open(path)? {
+                total_cost += cache.access(page?);
+                requests += 1;
+            }
+            file.write_rows([format!(
+                "{};{};{};{};{};{};{}",
+                n,
+                k,
+                strategy_name,
+                trace_name,
+                row_seed,
+                total_cost as f64 / requests as f64,
+                f64::NAN
+            )])
+        })?;
+
+    file.finish()
+}
+
+/// Converts a trace (text or binary) at `input` to the binary format at `output`.
+fn convert_trace(input: &Path, output: &Path) -> io::Result<()> {
+    let reader = TraceReader::open(input)?;
+    println!("Converting {:?} trace {}", reader.format(), input.display());
+    let mut writer = TraceWriter::create(output)?;
+    for page in reader {
+        writer.write(page?)?;
+    }
+    writer.finish()?;
     Ok(())
 }
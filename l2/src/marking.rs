@@ -0,0 +1,514 @@
+use crate::locality::AccessGraph;
+use crate::page_list::PageList;
+use crate::prediction::Predictions;
+use rand::Rng;
+use rand::rngs::StdRng;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+/// Enum representing how a marking algorithm picks the page to evict.
+#[allow(clippy::upper_case_acronyms, clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum MarkingEviction {
+    /// Evicts an unmarked page chosen uniformly at random (the randomized marking algorithm).
+    Random(StdRng),
+    /// Evicts the unmarked page that was loaded first.
+    FIFO,
+    /// Evicts the least recently used unmarked page. That page is always the least recently
+    /// used page overall, so this makes the same choices as plain LRU.
+    LRU,
+    /// A layer-based heuristic: follows the layer partition of the configurations the offline
+    /// optimum can be in and evicts a page chosen uniformly at random from the deepest layer
+    /// that holds cached pages. The cache always stays a configuration the optimum could be
+    /// in, which may mean evicting a marked page. This is not the probability distribution of
+    /// McGeoch and Sleator's Partition or of Equitable, so it carries no H(k) guarantee.
+    Layered(Layers, StdRng),
+    /// Evicts the unmarked page farthest in the access graph from every marked page, the
+    /// first in load order among equals. Pages outside the graph are the farthest of all.
+    FAR(Arc<AccessGraph>),
+    /// Predictive Marker of Lykouris and Vassilvitskii: evicts the unmarked page predicted to
+    /// be requested furthest in the future, until the chain of evictions the requested page
+    /// belongs to grows longer than H(k), and then evicts a random unmarked page.
+    Predictive(Chains, StdRng),
+    /// LMarker of Rohatgi: evicts the unmarked page predicted to be requested furthest in the
+    /// future on a request to a page new to the phase, and a random unmarked page on a request
+    /// to a page of the previous phase.
+    LMarker(Predictions, StdRng),
+}
+
+impl MarkingEviction {
+    /// Returns the name of the marking algorithm with this eviction policy.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarkingEviction::Random(_) => "RMA",
+            MarkingEviction::FIFO => "MARK-FIFO",
+            MarkingEviction::LRU => "MARK-LRU",
+            MarkingEviction::Layered(_, _) => "LAYERED",
+            MarkingEviction::FAR(_) => "FAR",
+            MarkingEviction::Predictive(_, _) => "PREDICTIVE-MARKER",
+            MarkingEviction::LMarker(_, _) => "LMARKER",
+        }
+    }
+}
+
+/// Statistics of one marking phase.
+///
+/// A phase is a maximal run of requests to at most `k` distinct pages; it depends on the
+/// request sequence alone, not on the eviction policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseStats {
+    /// The index of the request that started the phase.
+    pub start: usize,
+    /// The number of distinct pages requested in the phase but not in the previous one.
+    pub new_pages: usize,
+    /// The number of distinct pages requested in both the phase and the previous one.
+    pub stale_pages: usize,
+    /// The number of misses in the phase.
+    pub cost: usize,
+}
+
+/// A layer of `Layers`: a set of pages of which at least `count` more are cached.
+#[derive(Debug, Default)]
+struct Layer {
+    /// The pages of the layer.
+    pages: PageList,
+    /// The number of pages the layer adds to the required count of the layers before it.
+    count: usize,
+}
+
+/// The layer partition of the configurations the offline optimum can be in after the
+/// requests so far, at the least cost among all configurations.
+///
+/// Such a configuration holds every `forced` page, no page outside the layers, and for each
+/// `i` at least `layers[0].count + .. + layers[i].count` pages of the first `i + 1` layers.
+/// The counts add up to the capacity minus the number of forced pages.
+#[derive(Debug, Default)]
+pub struct Layers {
+    /// The pages every such configuration holds, in the order they were requested.
+    forced: PageList,
+    /// The remaining layers, most recently requested first.
+    layers: VecDeque<Layer>,
+}
+
+impl Layers {
+    /// Creates a new, empty `Layers`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the partition after a request to `page_id`.
+    ///
+    /// Takes O(k) time for a cache of capacity `k`.
+    fn _request(&mut self, page_id: usize, capacity: usize) {
+        if self.forced.contains(page_id) {
+            return;
+        }
+        if let Some(index) = self
+            .layers
+            .iter()
+            .position(|layer| layer.pages.contains(page_id))
+        {
+            // The optimum keeps its cost by holding the page: one page less is needed from
+            // the layer.
+            let layer = &mut self.layers[index];
+            layer.pages.remove(page_id);
+            layer.count -= 1;
+            self._merge_if_empty(index);
+        } else if self.layers.is_empty() && self.forced.len() < capacity {
+            // The cache is still filling up.
+            self.forced.push_back(page_id);
+            return;
+        } else {
+            // The optimum pays for the page and may evict any page it could have held.
+            let pages = std::mem::take(&mut self.forced);
+            let count = pages.len() - 1;
+            self.layers.push_front(Layer { pages, count });
+            self._merge_if_empty(0);
+        }
+        self.forced.push_back(page_id);
+    }
+
+    /// Merges the layer at `index` into the next one if its count dropped to zero. The
+    /// pages of a last layer have no room left and are dropped.
+    fn _merge_if_empty(&mut self, index: usize) {
+        if self.layers[index].count > 0 {
+            return;
+        }
+        let layer = self.layers.remove(index).unwrap();
+        if let Some(next) = self.layers.get_mut(index) {
+            for page in layer.pages.iter() {
+                next.pages.push_back(page);
+            }
+        }
+    }
+
+    /// Picks a cached page uniformly at random from the deepest layer that holds any.
+    ///
+    /// # Arguments
+    ///
+    /// * `memory` - The cached pages.
+    /// * `rng` - The random number generator.
+    fn _victim(&self, memory: &PageList, rng: &mut StdRng) -> usize {
+        for layer in self.layers.iter().rev() {
+            let cached: Vec<usize> = layer
+                .pages
+                .iter()
+                .filter(|&page| memory.contains(page))
+                .collect();
+            if !cached.is_empty() {
+                return cached[rng.random_range(0..cached.len())];
+            }
+        }
+        // Every cached page is forced: the optimum may evict any of them.
+        memory.get(rng.random_range(0..memory.len()))
+    }
+}
+
+/// The eviction chains of Predictive Marker in the current phase.
+///
+/// Each miss on a page new to the phase starts a chain; a page evicted to serve a miss joins
+/// the chain of that miss, and a miss on it later in the phase extends that chain.
+#[derive(Debug)]
+pub struct Chains {
+    /// The predicted next arrivals.
+    predictions: Predictions,
+    /// The length of each chain.
+    lengths: Vec<usize>,
+    /// The chain of each page evicted in the current phase.
+    chain_of: HashMap<usize, usize>,
+}
+
+impl Chains {
+    /// Creates a new `Chains` following the given predictions.
+    pub fn new(predictions: Predictions) -> Self {
+        Chains {
+            predictions,
+            lengths: Vec::new(),
+            chain_of: HashMap::new(),
+        }
+    }
+
+    /// Picks the unmarked page to evict for a miss on `page_id` and adds it to the chain of
+    /// the miss.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the missed page.
+    /// * `unmarked` - The cached pages not requested in the current phase.
+    /// * `capacity` - The maximum number of cached pages.
+    /// * `rng` - The random number generator.
+    fn _victim(
+        &mut self,
+        page_id: usize,
+        unmarked: &PageList,
+        capacity: usize,
+        rng: &mut StdRng,
+    ) -> usize {
+        let chain = match self.chain_of.get(&page_id) {
+            Some(&chain) => {
+                self.lengths[chain] += 1;
+                chain
+            }
+            None => {
+                self.lengths.push(1);
+                self.lengths.len() - 1
+            }
+        };
+        let harmonic: f64 = (1..=capacity).map(|i| 1.0 / i as f64).sum();
+        let victim = if self.lengths[chain] as f64 <= harmonic {
+            self.predictions.furthest(unmarked.iter()).unwrap()
+        } else {
+            unmarked.get(rng.random_range(0..unmarked.len()))
+        };
+        self.chain_of.insert(victim, chain);
+        victim
+    }
+}
+
+/// State of a marking algorithm: the pages marked in the current phase, the policy choosing
+/// among the unmarked ones, and the statistics of every phase so far.
+///
+/// A requested page is marked. When a page outside the `k` marked pages is requested, a new
+/// phase begins and every page is unmarked.
+#[derive(Debug)]
+pub struct MarkingPhases {
+    /// The eviction policy.
+    eviction: MarkingEviction,
+    /// The cached pages not requested in the current phase, in cache order.
+    unmarked: PageList,
+    /// The pages requested in the current phase.
+    marked: HashSet<usize>,
+    /// The pages requested in the previous phase.
+    previous: HashSet<usize>,
+    /// The statistics of every phase so far, the current one last.
+    phases: Vec<PhaseStats>,
+    /// The number of requests served.
+    time: usize,
+}
+
+impl MarkingPhases {
+    /// Creates a new `MarkingPhases` with the given eviction policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `eviction` - The policy picking the page to evict.
+    pub fn new(eviction: MarkingEviction) -> Self {
+        MarkingPhases {
+            eviction,
+            unmarked: PageList::new(),
+            marked: HashSet::new(),
+            previous: HashSet::new(),
+            phases: Vec::new(),
+            time: 0,
+        }
+    }
+
+    /// Returns the statistics of every phase so far, the current one last.
+    pub fn phases(&self) -> &[PhaseStats] {
+        &self.phases
+    }
+
+    /// Returns the eviction policy.
+    pub fn eviction(&self) -> &MarkingEviction {
+        &self.eviction
+    }
+
+    /// Forgets every phase and the state of the eviction policy, except its random number
+    /// generator, for a cache emptied to serve a new sequence.
+    pub fn reset(&mut self) {
+        self.unmarked.clear();
+        self.marked.clear();
+        self.previous.clear();
+        self.phases.clear();
+        self.time = 0;
+        match &mut self.eviction {
+            MarkingEviction::Random(_)
+            | MarkingEviction::FIFO
+            | MarkingEviction::LRU
+            | MarkingEviction::FAR(_) => {}
+            MarkingEviction::Layered(layers, _) => *layers = Layers::new(),
+            MarkingEviction::Predictive(chains, _) => {
+                chains.predictions.reset();
+                chains.lengths.clear();
+                chains.chain_of.clear();
+            }
+            MarkingEviction::LMarker(predictions, _) => predictions.reset(),
+        }
+    }
+
+    /// Forgets a page removed from the cache without a request. The page stays marked if it
+    /// was requested in the current phase.
+    pub fn invalidate(&mut self, page_id: usize) {
+        self.unmarked.remove(page_id);
+    }
+
+    /// Serves a request, marking the page and evicting from `memory` on a miss in a full cache.
+    ///
+    /// `memory` is kept in load order, or in recency order for the LRU policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `memory` - The cached pages.
+    /// * `capacity` - The maximum number of cached pages.
+    /// * `page_id` - The ID of the page being accessed.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access (0 for a hit, 1 for a miss).
+    pub fn access(&mut self, memory: &mut PageList, capacity: usize, page_id: usize) -> usize {
+        if !self.marked.contains(&page_id) {
+            if self.phases.is_empty() || self.marked.len() == capacity {
+                // A (k+1)-th distinct page starts a new phase: unmark every page.
+                self.previous = std::mem::take(&mut self.marked);
+                self.unmarked.clear();
+                for pid in memory.iter() {
+                    self.unmarked.push_back(pid);
+                }
+                self.phases.push(PhaseStats {
+                    start: self.time,
+                    ..PhaseStats::default()
+                });
+                if let MarkingEviction::Predictive(chains, _) = &mut self.eviction {
+                    chains.lengths.clear();
+                    chains.chain_of.clear();
+                }
+            }
+            self.marked.insert(page_id);
+            self.unmarked.remove(page_id);
+            let phase = self.phases.last_mut().unwrap();
+            if self.previous.contains(&page_id) {
+                phase.stale_pages += 1;
+            } else {
+                phase.new_pages += 1;
+            }
+        }
+        self.time += 1;
+
+        let cost = if memory.contains(page_id) {
+            if let MarkingEviction::LRU = self.eviction {
+                memory.move_to_back(page_id);
+            }
+            0
+        } else {
+            if memory.len() == capacity {
+                let victim = match &mut self.eviction {
+                    MarkingEviction::Random(rng) => {
+                        self.unmarked.get(rng.random_range(0..self.unmarked.len()))
+                    }
+                    MarkingEviction::Predictive(chains, rng) => {
+                        chains._victim(page_id, &self.unmarked, capacity, rng)
+                    }
+                    MarkingEviction::LMarker(predictions, rng) => {
+                        if self.previous.contains(&page_id) {
+                            self.unmarked.get(rng.random_range(0..self.unmarked.len()))
+                        } else {
+                            predictions.furthest(self.unmarked.iter()).unwrap()
+                        }
+                    }
+                    MarkingEviction::FIFO | MarkingEviction::LRU => self.unmarked.front().unwrap(),
+                    MarkingEviction::Layered(layers, rng) => layers._victim(memory, rng),
+                    MarkingEviction::FAR(graph) => {
+                        let distances = graph.distances(self.marked.iter().copied());
+                        let distance = |page_id: usize| {
+                            distances
+                                .get(page_id - 1)
+                                .copied()
+                                .flatten()
+                                .unwrap_or(usize::MAX)
+                        };
+                        // `max_by_key` keeps the last maximum, so scan back to front.
+                        let unmarked: Vec<usize> = self.unmarked.iter().collect();
+                        unmarked
+                            .into_iter()
+                            .rev()
+                            .max_by_key(|&page_id| distance(page_id))
+                            .unwrap()
+                    }
+                };
+                self.unmarked.remove(victim);
+                memory.remove(victim);
+            }
+            memory.push_back(page_id);
+            1
+        };
+        match &mut self.eviction {
+            MarkingEviction::Layered(layers, _) => layers._request(page_id, capacity),
+            MarkingEviction::Predictive(chains, _) => chains.predictions.observe(page_id),
+            MarkingEviction::LMarker(predictions, _) => predictions.observe(page_id),
+            _ => {}
+        }
+        self.phases.last_mut().unwrap().cost += cost;
+        cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::Predictor;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_phase_stats() {
+        let mut phases = MarkingPhases::new(MarkingEviction::FIFO);
+        let mut memory = PageList::new();
+        let requests = [1, 2, 1, 3, 2, 4, 1, 3, 4];
+        let costs: Vec<usize> = requests
+            .iter()
+            .map(|&page| phases.access(&mut memory, 2, page))
+            .collect();
+        // Phases: [1 2 1] [3 2] [4 1] [3 4]; FIFO among unmarked evicts 1, 2, 3, 4 and 1.
+        assert_eq!(costs, [1, 1, 0, 1, 0, 1, 1, 1, 1]);
+        let starts: Vec<usize> = phases.phases().iter().map(|phase| phase.start).collect();
+        assert_eq!(starts, [0, 3, 5, 7]);
+        let new_stale: Vec<(usize, usize)> = phases
+            .phases()
+            .iter()
+            .map(|phase| (phase.new_pages, phase.stale_pages))
+            .collect();
+        assert_eq!(new_stale, [(2, 0), (1, 1), (2, 0), (1, 1)]);
+        let total: usize = phases.phases().iter().map(|phase| phase.cost).sum();
+        assert_eq!(total, costs.iter().sum());
+    }
+
+    #[test]
+    fn test_far() {
+        let graph = Arc::new(AccessGraph::path(6));
+        let mut phases = MarkingPhases::new(MarkingEviction::FAR(graph));
+        let mut memory = PageList::new();
+        for page in [3, 4, 5, 6] {
+            phases.access(&mut memory, 3, page);
+        }
+        // Page 6 starts a phase, and 3 is the unmarked page farthest from it.
+        assert_eq!(memory.iter().collect::<Vec<_>>(), [4, 5, 6]);
+        // With 6 and 1 marked, 4 is two steps from either and 5 only one.
+        assert_eq!(phases.access(&mut memory, 3, 1), 1);
+        assert_eq!(memory.iter().collect::<Vec<_>>(), [5, 6, 1]);
+    }
+
+    #[test]
+    fn test_predictive_marking() {
+        let requests = [1, 2, 3, 1];
+        for eviction in [
+            MarkingEviction::Predictive(
+                Chains::new(Predictor::Oracle.predict(&requests)),
+                StdRng::seed_from_u64(0),
+            ),
+            MarkingEviction::LMarker(
+                Predictor::Oracle.predict(&requests),
+                StdRng::seed_from_u64(0),
+            ),
+        ] {
+            let mut phases = MarkingPhases::new(eviction);
+            let mut memory = PageList::new();
+            let costs: Vec<usize> = requests
+                .iter()
+                .map(|&page| phases.access(&mut memory, 2, page))
+                .collect();
+            // 3 starts a phase and evicts 2, which is never requested again, instead of 1.
+            assert_eq!(costs, [1, 1, 1, 0]);
+        }
+
+        // Misses on evicted pages extend a chain; past H(2) = 1.5 it evicts at random.
+        let mut chains = Chains::new(Predictor::Oracle.predict(&[]));
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut unmarked = PageList::new();
+        unmarked.push_back(4);
+        unmarked.push_back(5);
+        assert_eq!(chains._victim(3, &unmarked, 2, &mut rng), 4);
+        let victim = chains._victim(4, &unmarked, 2, &mut rng);
+        assert_eq!(chains.lengths, [2]);
+        assert_eq!(chains.chain_of[&victim], 0);
+        assert_eq!(chains._victim(6, &unmarked, 2, &mut rng), 4);
+        assert_eq!(chains.lengths, [2, 1]);
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut layers = Layers::new();
+        for page in [1, 2, 3, 4, 5, 1, 6] {
+            layers._request(page, 3);
+        }
+        // The optimum holds 6, one of {5, 1} and one more of {5, 1, 2, 3, 4}.
+        assert_eq!(layers.forced.iter().collect::<Vec<_>>(), [6]);
+        let layer_pages: Vec<(HashSet<usize>, usize)> = layers
+            .layers
+            .iter()
+            .map(|layer| (layer.pages.iter().collect(), layer.count))
+            .collect();
+        assert_eq!(
+            layer_pages,
+            [(HashSet::from([1, 5]), 1), (HashSet::from([2, 3, 4]), 1)]
+        );
+
+        // Holding 2 at no extra cost leaves no room for 3 and 4.
+        layers._request(2, 3);
+        assert_eq!(layers.forced.iter().collect::<Vec<_>>(), [6, 2]);
+        assert_eq!(layers.layers.len(), 1);
+        assert_eq!(
+            layers.layers[0].pages.iter().collect::<HashSet<_>>(),
+            HashSet::from([1, 5])
+        );
+        assert_eq!(layers.layers[0].count, 1);
+    }
+}
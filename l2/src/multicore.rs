@@ -0,0 +1,302 @@
+use crate::cache::{CacheManagementStrategy, CacheManager};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+/// Enum representing how a `MulticoreCache` divides its capacity among the request streams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Partitioning {
+    /// One cache shared by every stream, evicting pages of any stream.
+    Shared,
+    /// One cache per stream with the given fixed capacities.
+    Static(Vec<usize>),
+    /// One cache per stream, resized every `epoch` requests. Each stream starts with an equal
+    /// share; at the end of an epoch every stream gets `minimum` pages and the rest is handed
+    /// out page by page to the stream that would have gained the most LRU hits from it during
+    /// the epoch, as in utility-based cache partitioning.
+    Dynamic {
+        /// The number of requests between two resizes.
+        epoch: usize,
+        /// The smallest partition a stream can get.
+        minimum: usize,
+    },
+}
+
+/// Produces the strategy of each cache a `MulticoreCache` builds, from the index of the build.
+struct StrategySource<'a>(Box<dyn Fn(usize) -> CacheManagementStrategy + 'a>);
+
+impl std::fmt::Debug for StrategySource<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StrategySource")
+    }
+}
+
+/// A cache serving `p` interleaved request streams, shared or partitioned among them.
+///
+/// The streams have disjoint pages: page `x` of one stream is a different page from page
+/// `x` of another. Resizing a partition builds a new cache for the stream and warms it with
+/// the stream's most recently requested pages that were cached, at no cost, so the strategy
+/// forgets everything else it knew.
+#[derive(Debug)]
+pub struct MulticoreCache<'a> {
+    /// The caches: one shared cache, or one per stream.
+    caches: Vec<CacheManager>,
+    /// The capacity of each cache.
+    capacities: Vec<usize>,
+    /// The total capacity.
+    capacity: usize,
+    /// How the capacity is divided among the streams.
+    partitioning: Partitioning,
+    /// The strategy of each cache that is built.
+    strategy: StrategySource<'a>,
+    /// The number of caches built so far.
+    builds: usize,
+    /// The number of requests of each stream.
+    requests: Vec<usize>,
+    /// The number of faults of each stream.
+    faults: Vec<usize>,
+    /// The most recently requested pages of each stream, most recent first, up to the total
+    /// capacity. Only maintained for dynamic partitioning.
+    recency: Vec<Vec<usize>>,
+    /// The number of requests of each stream in the current epoch that hit at each LRU stack
+    /// distance. Only maintained for dynamic partitioning.
+    histograms: Vec<Vec<usize>>,
+    /// The number of requests served.
+    time: usize,
+}
+
+impl<'a> MulticoreCache<'a> {
+    /// Creates a new `MulticoreCache`.
+    ///
+    /// # Arguments
+    ///
+    /// * `streams` - The number of request streams.
+    /// * `capacity` - The total number of pages the caches hold.
+    /// * `partitioning` - How the capacity is divided among the streams.
+    /// * `strategy` - Produces the strategy of each cache that is built, from the index of
+    ///   the build, so randomized strategies can be seeded differently.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `MulticoreCache`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if static partitions are not one non-empty partition per stream within the
+    /// total capacity, or if dynamic partitions cannot give every stream its minimum.
+    pub fn new(
+        streams: usize,
+        capacity: usize,
+        partitioning: Partitioning,
+        strategy: impl Fn(usize) -> CacheManagementStrategy + 'a,
+    ) -> Self {
+        let capacities = match &partitioning {
+            Partitioning::Shared => vec![capacity],
+            Partitioning::Static(capacities) => {
+                assert!(
+                    capacities.len() == streams
+                        && capacities.iter().all(|&c| c > 0)
+                        && capacities.iter().sum::<usize>() <= capacity,
+                    "Static partitions must give each stream a page within the capacity"
+                );
+                capacities.clone()
+            }
+            Partitioning::Dynamic { minimum, .. } => {
+                assert!(
+                    *minimum > 0 && capacity >= streams * minimum,
+                    "Every stream needs its minimum partition"
+                );
+                (0..streams)
+                    .map(|stream| capacity / streams + usize::from(stream < capacity % streams))
+                    .collect()
+            }
+        };
+        let caches = capacities
+            .iter()
+            .enumerate()
+            .map(|(build, &c)| CacheManager::new(c, strategy(build)))
+            .collect();
+        MulticoreCache {
+            caches,
+            builds: capacities.len(),
+            capacities,
+            capacity,
+            partitioning,
+            strategy: StrategySource(Box::new(strategy)),
+            requests: vec![0; streams],
+            faults: vec![0; streams],
+            recency: vec![Vec::new(); streams],
+            histograms: vec![vec![0; capacity]; streams],
+            time: 0,
+        }
+    }
+
+    /// Returns the number of requests of each stream.
+    pub fn requests(&self) -> &[usize] {
+        &self.requests
+    }
+
+    /// Returns the number of faults of each stream.
+    pub fn faults(&self) -> &[usize] {
+        &self.faults
+    }
+
+    /// Returns the current capacity of each cache: the total capacity for a shared cache,
+    /// or the partition of each stream.
+    pub fn capacities(&self) -> &[usize] {
+        &self.capacities
+    }
+
+    /// Serves a request of one stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - The index of the stream.
+    /// * `page_id` - The ID of the page being accessed, within the stream.
+    ///
+    /// # Returns
+    ///
+    /// The cost of the access (0 for a hit, 1 for a miss).
+    pub fn access(&mut self, stream: usize, page_id: usize) -> usize {
+        self.time += 1;
+        self.requests[stream] += 1;
+        if let Partitioning::Dynamic { .. } = self.partitioning {
+            let recency = &mut self.recency[stream];
+            if let Some(distance) = recency.iter().position(|&pid| pid == page_id) {
+                self.histograms[stream][distance] += 1;
+                recency.remove(distance);
+            }
+            recency.insert(0, page_id);
+            recency.truncate(self.capacity);
+        }
+
+        let cost = match self.partitioning {
+            Partitioning::Shared => self.caches[0].access(page_id * self.requests.len() + stream),
+            _ => self.caches[stream].access(page_id),
+        };
+        self.faults[stream] += cost;
+
+        if let Partitioning::Dynamic { epoch, minimum } = self.partitioning
+            && self.time.is_multiple_of(epoch)
+        {
+            self._repartition(minimum);
+        }
+        cost
+    }
+
+    /// Hands out the capacity by the marginal LRU hits of the epoch and rebuilds the
+    /// partitions whose capacity changed.
+    fn _repartition(&mut self, minimum: usize) {
+        let streams = self.requests.len();
+        let mut capacities = vec![minimum; streams];
+        for _ in streams * minimum..self.capacity {
+            let stream = (0..streams)
+                .max_by_key(|&s| (self.histograms[s][capacities[s]], std::cmp::Reverse(s)))
+                .unwrap();
+            capacities[stream] += 1;
+        }
+        for (stream, &capacity) in capacities.iter().enumerate() {
+            if capacity != self.capacities[stream] {
+                let strategy = (self.strategy.0)(self.builds);
+                self.builds += 1;
+                let mut cache = CacheManager::new(capacity, strategy);
+                let old = &self.caches[stream];
+                let warm: Vec<usize> = self.recency[stream]
+                    .iter()
+                    .copied()
+                    .filter(|&pid| old.contains(pid))
+                    .take(capacity)
+                    .collect();
+                // Least recent first, so the most recent pages end up the most recent.
+                for &pid in warm.iter().rev() {
+                    cache.access(pid);
+                }
+                self.caches[stream] = cache;
+            }
+            self.histograms[stream].fill(0);
+        }
+        self.capacities = capacities;
+    }
+}
+
+/// Interleaves request streams at random: each request comes from a stream chosen uniformly
+/// at random among those with requests left, so every stream keeps its own order.
+///
+/// # Arguments
+///
+/// * `streams` - The request sequence of each stream.
+/// * `seed` - The seed of the random number generator.
+///
+/// # Returns
+///
+/// The interleaved requests as (stream, page) pairs.
+pub fn interleave(streams: &[Vec<usize>], seed: u64) -> Vec<(usize, usize)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut next = vec![0; streams.len()];
+    let mut active: Vec<usize> = (0..streams.len())
+        .filter(|&stream| !streams[stream].is_empty())
+        .collect();
+    let mut requests = Vec::with_capacity(streams.iter().map(Vec::len).sum());
+    while !active.is_empty() {
+        let index = rng.random_range(0..active.len());
+        let stream = active[index];
+        requests.push((stream, streams[stream][next[stream]]));
+        next[stream] += 1;
+        if next[stream] == streams[stream].len() {
+            active.swap_remove(index);
+        }
+    }
+    requests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A stream cycling through 3 pages and a stream scanning fresh pages, interleaved.
+    fn cycle_and_scan() -> Vec<(usize, usize)> {
+        let cycle: Vec<usize> = (0..600).map(|i| i % 3 + 1).collect();
+        let scan: Vec<usize> = (1..=600).collect();
+        interleave(&[cycle, scan], 0)
+    }
+
+    #[test]
+    fn test_interleave() {
+        let requests = cycle_and_scan();
+        assert_eq!(requests.len(), 1200);
+        let scan: Vec<usize> = requests
+            .iter()
+            .filter(|&&(stream, _)| stream == 1)
+            .map(|&(_, page)| page)
+            .collect();
+        assert_eq!(scan, (1..=600).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_partitioning() {
+        let requests = cycle_and_scan();
+        let faults = |partitioning| {
+            let mut cache =
+                MulticoreCache::new(2, 4, partitioning, |_| CacheManagementStrategy::LRU);
+            for &(stream, page) in &requests {
+                cache.access(stream, page);
+            }
+            assert_eq!(cache.requests(), [600, 600]);
+            (cache.faults().to_vec(), cache.capacities().to_vec())
+        };
+
+        // The scan keeps pushing the cycle's pages out of a shared cache.
+        let (shared, _) = faults(Partitioning::Shared);
+        assert!(shared[0] > 30, "{:?}", shared);
+        assert_eq!(shared[1], 600);
+        // A partition of 3 pages protects the cycle completely.
+        let (fixed, _) = faults(Partitioning::Static(vec![3, 1]));
+        assert_eq!(fixed, [3, 600]);
+        // Dynamic partitioning finds that split after its first epoch.
+        let (dynamic, capacities) = faults(Partitioning::Dynamic {
+            epoch: 100,
+            minimum: 1,
+        });
+        assert_eq!(capacities, [3, 1]);
+        assert!(dynamic[0] < shared[0], "{:?}", dynamic);
+    }
+}
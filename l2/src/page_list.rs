@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+/// A node of the `PageList` arena.
+#[derive(Debug, Clone)]
+struct Node {
+    /// The ID of the page stored in this node.
+    page_id: usize,
+    /// The slot of the previous node in list order.
+    prev: Option<usize>,
+    /// The slot of the next node in list order.
+    next: Option<usize>,
+}
+
+/// An ordered set of pages with O(1) membership, insertion, removal and reordering.
+///
+/// The pages form a doubly linked list whose nodes are stored densely in a vector, indexed
+/// by a hash map from page ID to slot. Removing a page moves the last node into the freed
+/// slot, so the slots always cover `0..len()` and `get` can pick a page uniformly at random.
+#[derive(Debug, Clone, Default)]
+pub struct PageList {
+    /// The nodes, densely packed in slots `0..len()`.
+    nodes: Vec<Node>,
+    /// The slot of each page in the list.
+    slots: HashMap<usize, usize>,
+    /// The slot of the front (oldest) page.
+    head: Option<usize>,
+    /// The slot of the back (newest) page.
+    tail: Option<usize>,
+}
+
+impl PageList {
+    /// Creates a new, empty `PageList`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty `PageList` with room for `capacity` pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of pages to reserve space for.
+    pub fn with_capacity(capacity: usize) -> Self {
+        PageList {
+            nodes: Vec::with_capacity(capacity),
+            slots: HashMap::with_capacity(capacity),
+            head: None,
+            tail: None,
+        }
+    }
+
+    /// Returns the number of pages in the list.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the list holds no pages.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns `true` if the page is in the list.
+    pub fn contains(&self, page_id: usize) -> bool {
+        self.slots.contains_key(&page_id)
+    }
+
+    /// Returns the page at the front of the list, if any.
+    pub fn front(&self) -> Option<usize> {
+        self.head.map(|slot| self.nodes[slot].page_id)
+    }
+
+    /// Returns the page stored in the given slot.
+    ///
+    /// Slots are not in list order; together they cover `0..len()`, which makes this the
+    /// way to pick a page uniformly at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slot >= len()`.
+    pub fn get(&self, slot: usize) -> usize {
+        self.nodes[slot].page_id
+    }
+
+    /// Appends a page to the back of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the page is already in the list.
+    pub fn push_back(&mut self, page_id: usize) {
+        let slot = self.nodes.len();
+        assert!(
+            self.slots.insert(page_id, slot).is_none(),
+            "Page {} is already in the list",
+            page_id
+        );
+        self.nodes.push(Node {
+            page_id,
+            prev: self.tail,
+            next: None,
+        });
+        self._link_back(slot);
+    }
+
+    /// Removes and returns the page at the front of the list.
+    pub fn pop_front(&mut self) -> Option<usize> {
+        let page_id = self.front()?;
+        self.remove(page_id);
+        Some(page_id)
+    }
+
+    /// Removes a page from the list.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the page was in the list.
+    pub fn remove(&mut self, page_id: usize) -> bool {
+        let Some(slot) = self.slots.remove(&page_id) else {
+            return false;
+        };
+        self._unlink(slot);
+        let last = self.nodes.len() - 1;
+        self.nodes.swap_remove(slot);
+        if slot != last {
+            // The former last node now lives in `slot`; repoint everything that referred to it.
+            let Node {
+                page_id,
+                prev,
+                next,
+            } = self.nodes[slot];
+            self.slots.insert(page_id, slot);
+            match prev {
+                Some(prev) => self.nodes[prev].next = Some(slot),
+                None => self.head = Some(slot),
+            }
+            match next {
+                Some(next) => self.nodes[next].prev = Some(slot),
+                None => self.tail = Some(slot),
+            }
+        }
+        true
+    }
+
+    /// Moves a page to the back of the list.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the page was in the list.
+    pub fn move_to_back(&mut self, page_id: usize) -> bool {
+        let Some(&slot) = self.slots.get(&page_id) else {
+            return false;
+        };
+        if self.tail != Some(slot) {
+            self._unlink(slot);
+            self.nodes[slot].prev = self.tail;
+            self._link_back(slot);
+        }
+        true
+    }
+
+    /// Removes every page from the list.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.slots.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    /// Returns an iterator over the pages from front to back.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.head, |&slot| self.nodes[slot].next)
+            .map(|slot| self.nodes[slot].page_id)
+    }
+
+    /// Links a detached node, whose `prev` already points at the tail, as the new tail.
+    fn _link_back(&mut self, slot: usize) {
+        self.nodes[slot].next = None;
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(slot),
+            None => self.head = Some(slot),
+        }
+        self.tail = Some(slot);
+    }
+
+    /// Detaches a node from its neighbours, leaving it in its slot.
+    fn _unlink(&mut self, slot: usize) {
+        let Node { prev, next, .. } = self.nodes[slot];
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_list() {
+        let mut list = PageList::new();
+        for page_id in 1..=5 {
+            list.push_back(page_id);
+        }
+        assert!(list.remove(2));
+        assert!(!list.remove(2));
+        assert!(list.move_to_back(1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), [3, 4, 5, 1]);
+        assert_eq!(list.pop_front(), Some(3));
+        assert!(list.move_to_back(1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), [4, 5, 1]);
+
+        // Slots stay dense, so every page is reachable through `get`.
+        let mut pages: Vec<usize> = (0..list.len()).map(|slot| list.get(slot)).collect();
+        pages.sort();
+        assert_eq!(pages, [1, 4, 5]);
+
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+    }
+}
@@ -0,0 +1,198 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+use std::collections::HashMap;
+
+/// Enum representing where the predicted next arrivals of a learning-augmented strategy come
+/// from.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum Predictor {
+    /// Predicts every next arrival exactly.
+    Oracle,
+    /// Adds Gaussian noise with the given standard deviation, in requests, to every exact
+    /// prediction, keeping it after the request it is made at. Pages that are never
+    /// requested again are predicted exactly.
+    Noisy(f64, StdRng),
+}
+
+impl Predictor {
+    /// Predicts the next arrival of the page of every request.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - The full sequence of page requests that will be served.
+    ///
+    /// # Returns
+    ///
+    /// The predictions, to be fed the same requests.
+    pub fn predict(self, requests: &[usize]) -> Predictions {
+        let mut predicted = next_arrivals(requests);
+        if let Predictor::Noisy(sigma, mut rng) = self {
+            for (t, next) in predicted.iter_mut().enumerate() {
+                if *next != usize::MAX {
+                    let noisy = *next as f64 + sigma * _gaussian(&mut rng);
+                    *next = (noisy.round().max(0.0) as usize).max(t + 1);
+                }
+            }
+        }
+        Predictions::new(predicted)
+    }
+}
+
+/// Draws a standard normal sample by the Box-Muller transform.
+fn _gaussian(rng: &mut StdRng) -> f64 {
+    let radius = (-2.0 * (1.0 - rng.random::<f64>()).ln()).sqrt();
+    radius * (2.0 * std::f64::consts::PI * rng.random::<f64>()).cos()
+}
+
+/// Computes the next arrival of the page of every request in O(n).
+///
+/// # Arguments
+///
+/// * `requests` - The sequence of page requests.
+///
+/// # Returns
+///
+/// The index of the next request to the page requested at `t`, at index `t`, or
+/// `usize::MAX` if it is never requested again.
+pub fn next_arrivals(requests: &[usize]) -> Vec<usize> {
+    let mut next_arrivals = vec![usize::MAX; requests.len()];
+    let mut last_seen: HashMap<usize, usize> = HashMap::new();
+    for (t, &page_id) in requests.iter().enumerate().rev() {
+        if let Some(next) = last_seen.insert(page_id, t) {
+            next_arrivals[t] = next;
+        }
+    }
+    next_arrivals
+}
+
+/// Computes the total prediction error η of Lykouris and Vassilvitskii: the sum over all
+/// requests of the distance between the predicted and the actual next arrival. Requests whose
+/// page is never requested again, or predicted never to be, are left out.
+///
+/// # Arguments
+///
+/// * `requests` - The sequence of page requests.
+/// * `predictions` - The predictions made for the sequence.
+pub fn prediction_error(requests: &[usize], predictions: &Predictions) -> usize {
+    next_arrivals(requests)
+        .into_iter()
+        .zip(&predictions.predicted)
+        .filter(|&(actual, &predicted)| actual != usize::MAX && predicted != usize::MAX)
+        .map(|(actual, &predicted)| actual.abs_diff(predicted))
+        .sum()
+}
+
+/// Predicted next arrivals of a request sequence used by the learning-augmented strategies.
+///
+/// The strategy must be fed exactly the sequence the predictions were made for, one request
+/// per access.
+#[derive(Debug, Clone)]
+pub struct Predictions {
+    /// `predicted[t]` is the predicted index of the next request to the page requested at
+    /// `t`, or `usize::MAX` if it is predicted never to be requested again.
+    predicted: Vec<usize>,
+    /// The index of the next request to be served.
+    time: usize,
+    /// The latest predicted next arrival of every page requested so far.
+    next: HashMap<usize, usize>,
+}
+
+impl Predictions {
+    /// Creates predictions from the predicted next arrival of the page of every request, as
+    /// made by a `Predictor` or by a model outside this crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicted` - The predicted index of the next request to the page requested at `t`,
+    ///   at index `t`, or `usize::MAX` for never.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Predictions`.
+    pub fn new(predicted: Vec<usize>) -> Self {
+        Predictions {
+            predicted,
+            time: 0,
+            next: HashMap::new(),
+        }
+    }
+
+    /// Forgets the predictions observed so far, to serve the sequence again from its first
+    /// request.
+    pub fn reset(&mut self) {
+        self.time = 0;
+        self.next.clear();
+    }
+
+    /// Records the prediction made at the current request, which must be to the given page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called past the end of the predicted sequence.
+    pub fn observe(&mut self, page_id: usize) {
+        assert!(
+            self.time < self.predicted.len(),
+            "observe called past the end of the predicted sequence"
+        );
+        self.next.insert(page_id, self.predicted[self.time]);
+        self.time += 1;
+    }
+
+    /// Returns the page predicted to be requested furthest in the future, the first among
+    /// equals, or `None` if there are no pages. Pages never requested so far count as never
+    /// requested again.
+    pub fn furthest(&self, pages: impl Iterator<Item = usize>) -> Option<usize> {
+        pages.min_by_key(|page_id| {
+            std::cmp::Reverse(self.next.get(page_id).copied().unwrap_or(usize::MAX))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_predictions() {
+        let requests = [1, 2, 1, 3, 2, 1];
+        assert_eq!(
+            next_arrivals(&requests),
+            [2, 4, 5, usize::MAX, usize::MAX, usize::MAX]
+        );
+        let mut predictions = Predictor::Oracle.predict(&requests);
+        assert_eq!(prediction_error(&requests, &predictions), 0);
+        for page in [1, 2, 1] {
+            predictions.observe(page);
+        }
+        // 2 comes back at index 4 and 1 at index 5; 3 has not been requested.
+        assert_eq!(predictions.furthest([2, 1].into_iter()), Some(1));
+        assert_eq!(predictions.furthest([2, 1, 3].into_iter()), Some(3));
+        assert_eq!(predictions.furthest(std::iter::empty()), None);
+
+        // Noisy predictions stay in the future and err more with more noise.
+        let requests: Vec<usize> = (0..10_000).map(|i| (i * i * 7 + i * 3 + 1) % 50).collect();
+        let errors: Vec<usize> = [0.0, 10.0, 100.0]
+            .into_iter()
+            .map(|sigma| {
+                let predictions =
+                    Predictor::Noisy(sigma, StdRng::seed_from_u64(0)).predict(&requests);
+                assert!(
+                    predictions
+                        .predicted
+                        .iter()
+                        .enumerate()
+                        .all(|(t, &next)| next > t)
+                );
+                prediction_error(&requests, &predictions)
+            })
+            .collect();
+        assert_eq!(errors[0], 0);
+        assert!(
+            errors[0] < errors[1] && errors[1] < errors[2],
+            "{:?}",
+            errors
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of a binary trace, followed by the format version.
+const BINARY_MAGIC: &[u8; 4] = b"PTRC";
+/// The version of the binary trace format written by `TraceWriter`.
+const BINARY_VERSION: u8 = 1;
+
+/// Enum representing the on-disk formats of a page-reference trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// Plain text: one page id per line. Empty lines and lines starting with `#` are ignored.
+    Text,
+    /// Compact binary: the magic `PTRC`, a version byte, then one LEB128 varint per page id.
+    Binary,
+}
+
+/// A streaming reader over the page ids of a trace.
+///
+/// Only one buffer of the file is held in memory at a time, so traces of any length can be
+/// replayed. Yields an error for malformed input instead of panicking.
+#[derive(Debug)]
+pub struct TraceReader<R: BufRead> {
+    /// The underlying buffered input, positioned after the header.
+    input: R,
+    /// The format of the trace.
+    format: TraceFormat,
+    /// The current line, reused between text records.
+    line: String,
+    /// The number of text lines read so far, used in error messages.
+    line_number: usize,
+}
+
+impl TraceReader<BufReader<File>> {
+    /// Opens the trace at `path`, detecting its format from the first bytes of the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the trace file.
+    ///
+    /// # Returns
+    ///
+    /// A reader over the page ids of the trace.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        TraceReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: BufRead> TraceReader<R> {
+    /// Creates a new `TraceReader`, detecting the format from the first bytes of `input`.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The buffered trace input.
+    ///
+    /// # Returns
+    ///
+    /// A reader over the page ids of the trace.
+    pub fn new(mut input: R) -> io::Result<Self> {
+        let format = if input.fill_buf()?.starts_with(BINARY_MAGIC) {
+            input.consume(BINARY_MAGIC.len());
+            let mut version = [0];
+            input.read_exact(&mut version)?;
+            if version[0] != BINARY_VERSION {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unsupported binary trace version {}", version[0]),
+                ));
+            }
+            TraceFormat::Binary
+        } else {
+            TraceFormat::Text
+        };
+        Ok(TraceReader {
+            input,
+            format,
+            line: String::new(),
+            line_number: 0,
+        })
+    }
+
+    /// Returns the detected format of the trace.
+    pub fn format(&self) -> TraceFormat {
+        self.format
+    }
+
+    /// Reads the next page id of a text trace.
+    fn _next_text(&mut self) -> Option<io::Result<usize>> {
+        loop {
+            self.line.clear();
+            match self.input.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.line_number += 1,
+                Err(e) => return Some(Err(e)),
+            }
+            let record = self.line.trim();
+            if record.is_empty() || record.starts_with('#') {
+                continue;
+            }
+            return Some(record.parse().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid page id {:?} on line {}", record, self.line_number),
+                )
+            }));
+        }
+    }
+
+    /// Reads the next page id of a binary trace, decoding one LEB128 varint.
+    fn _next_binary(&mut self) -> Option<io::Result<usize>> {
+        let mut page_id: usize = 0;
+        let mut shift = 0;
+        loop {
+            let byte = match self.input.fill_buf() {
+                Ok([]) if shift == 0 => return None,
+                Ok([]) => {
+                    return Some(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Truncated page id at the end of the trace",
+                    )));
+                }
+                Ok(buf) => buf[0],
+                Err(e) => return Some(Err(e)),
+            };
+            self.input.consume(1);
+            if shift >= usize::BITS {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Page id does not fit in usize",
+                )));
+            }
+            page_id |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Some(Ok(page_id));
+            }
+            shift += 7;
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for TraceReader<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.format {
+            TraceFormat::Text => self._next_text(),
+            TraceFormat::Binary => self._next_binary(),
+        }
+    }
+}
+
+/// A writer producing binary traces readable by `TraceReader`.
+#[derive(Debug)]
+pub struct TraceWriter<W: Write> {
+    /// The underlying output, positioned after the header.
+    output: W,
+}
+
+impl TraceWriter<BufWriter<File>> {
+    /// Creates the binary trace file at `path`, truncating it if it exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the trace file.
+    ///
+    /// # Returns
+    ///
+    /// A writer appending page ids to the new trace.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        TraceWriter::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> TraceWriter<W> {
+    /// Creates a new `TraceWriter`, writing the binary header to `output`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The trace output.
+    ///
+    /// # Returns
+    ///
+    /// A writer appending page ids to the trace.
+    pub fn new(mut output: W) -> io::Result<Self> {
+        output.write_all(BINARY_MAGIC)?;
+        output.write_all(&[BINARY_VERSION])?;
+        Ok(TraceWriter { output })
+    }
+
+    /// Appends one page id to the trace as a LEB128 varint.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the requested page.
+    pub fn write(&mut self, mut page_id: usize) -> io::Result<()> {
+        let mut buf = [0u8; 10];
+        let mut len = 0;
+        loop {
+            let byte = (page_id & 0x7f) as u8;
+            page_id >>= 7;
+            if page_id == 0 {
+                buf[len] = byte;
+                len += 1;
+                break;
+            }
+            buf[len] = byte | 0x80;
+            len += 1;
+        }
+        self.output.write_all(&buf[..len])
+    }
+
+    /// Flushes the trace and returns the underlying output.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.output.flush()?;
+        Ok(self.output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_trace() {
+        let input = "# page references\n1\n  2 \n\n300\n1\n";
+        let reader = TraceReader::new(input.as_bytes()).unwrap();
+        assert_eq!(reader.format(), TraceFormat::Text);
+        let pages: Vec<usize> = reader.map(|page| page.unwrap()).collect();
+        assert_eq!(pages, [1, 2, 300, 1]);
+
+        let mut reader = TraceReader::new("1\nx\n".as_bytes()).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap(), 1);
+        assert!(reader.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_binary_trace() {
+        let pages = [0, 1, 127, 128, 300, 1 << 40, usize::MAX];
+        let mut writer = TraceWriter::new(Vec::new()).unwrap();
+        for &page in &pages {
+            writer.write(page).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+        let reader = TraceReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.format(), TraceFormat::Binary);
+        let read: Vec<usize> = reader.map(|page| page.unwrap()).collect();
+        assert_eq!(read, pages);
+
+        // A varint cut off in the middle is reported, not silently dropped.
+        let mut reader = TraceReader::new(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(reader.any(|page| page.is_err()));
+    }
+}
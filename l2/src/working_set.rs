@@ -0,0 +1,215 @@
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::ops::RangeInclusive;
+
+/// A request generator that moves between working sets: each phase requests pages drawn
+/// uniformly at random from the current working set, and at the end of a phase the set is
+/// replaced by one that keeps some of its pages and adds fresh ones.
+#[derive(Debug)]
+pub struct WorkingSets {
+    /// The number of pages in a working set.
+    size: usize,
+    /// The number of pages a working set keeps from the previous one.
+    overlap: usize,
+    /// The range the length of each phase is drawn from uniformly at random.
+    phase_lengths: RangeInclusive<usize>,
+    /// The pages of the current working set.
+    pages: Vec<usize>,
+    /// The ID of the next fresh page.
+    next_page: usize,
+    /// The number of requests left in the current phase.
+    remaining: usize,
+    /// The number of requests drawn so far.
+    time: usize,
+    /// The indices of the requests that started a new working set, after the first.
+    shifts: Vec<usize>,
+    /// The random number generator.
+    rng: StdRng,
+}
+
+impl WorkingSets {
+    /// Creates a new `WorkingSets` generator. Pages are numbered from 1 in the order they
+    /// first join a working set.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of pages in a working set.
+    /// * `overlap` - The number of pages a working set keeps from the previous one, chosen
+    ///   uniformly at random.
+    /// * `phase_lengths` - The range of the number of requests in a phase.
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `overlap >= size` or if the phase lengths include 0.
+    pub fn new(
+        size: usize,
+        overlap: usize,
+        phase_lengths: RangeInclusive<usize>,
+        seed: u64,
+    ) -> Self {
+        assert!(overlap < size, "A working set must change at every shift");
+        assert!(
+            *phase_lengths.start() > 0 && !phase_lengths.is_empty(),
+            "Phases must hold at least one request"
+        );
+        WorkingSets {
+            size,
+            overlap,
+            phase_lengths,
+            pages: Vec::with_capacity(size),
+            next_page: 1,
+            remaining: 0,
+            time: 0,
+            shifts: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the indices of the requests that started a new working set, after the first.
+    pub fn shifts(&self) -> &[usize] {
+        &self.shifts
+    }
+
+    /// Samples the next request, starting a new phase when the current one is over.
+    pub fn sample(&mut self) -> usize {
+        if self.remaining == 0 {
+            let kept = if self.time == 0 {
+                0
+            } else {
+                self.shifts.push(self.time);
+                self.overlap
+            };
+            self.pages.shuffle(&mut self.rng);
+            self.pages.truncate(kept);
+            while self.pages.len() < self.size {
+                self.pages.push(self.next_page);
+                self.next_page += 1;
+            }
+            self.remaining = self.rng.random_range(self.phase_lengths.clone());
+        }
+        self.remaining -= 1;
+        self.time += 1;
+        self.pages[self.rng.random_range(0..self.size)]
+    }
+}
+
+/// How a strategy recovers from the shifts of a working-set workload, from its miss rate
+/// `d` requests after a shift averaged over all shifts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recovery {
+    /// The highest miss rate over `window` consecutive requests after a shift.
+    pub spike: f64,
+    /// The miss rate over the second half of a phase, once the strategy has settled.
+    pub baseline: f64,
+    /// The number of requests after a shift until the miss rate over the next `window`
+    /// requests, past its peak, falls within 10% of the spike above the baseline, or the
+    /// shortest phase length if it never does.
+    pub recovery_time: usize,
+}
+
+/// Measures how a strategy recovers from the shifts of a working-set workload.
+///
+/// # Arguments
+///
+/// * `costs` - The cost of every request.
+/// * `shifts` - The indices of the requests that started a new working set. The phase after
+///   the last shift is left out, as the end of `costs` may cut it short.
+/// * `window` - The number of requests miss rates are measured over.
+///
+/// # Returns
+///
+/// The spike, baseline and recovery time of the miss rate after a shift.
+///
+/// # Panics
+///
+/// Panics if there are fewer than two shifts or if a phase between two shifts holds fewer
+/// than `2 * window` requests.
+pub fn recovery(costs: &[usize], shifts: &[usize], window: usize) -> Recovery {
+    assert!(shifts.len() >= 2, "Recovery needs at least two shifts");
+    let length = shifts
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .min()
+        .unwrap();
+    let shifts = &shifts[..shifts.len() - 1];
+    assert!(
+        length >= 2 * window,
+        "Phases must hold at least two windows"
+    );
+
+    // The miss rate `d` requests after a shift, over all shifts.
+    let curve: Vec<f64> = (0..length)
+        .map(|d| shifts.iter().map(|&start| costs[start + d]).sum::<usize>() as f64)
+        .map(|misses| misses / shifts.len() as f64)
+        .collect();
+    let rates: Vec<f64> = curve
+        .windows(window)
+        .map(|rates| rates.iter().sum::<f64>() / window as f64)
+        .collect();
+    let baseline = curve[length / 2..].iter().sum::<f64>() / (length - length / 2) as f64;
+    let (peak, &spike) = rates
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap();
+    let threshold = baseline + 0.1 * (spike - baseline);
+    let recovery_time = if spike <= baseline {
+        0
+    } else {
+        rates[peak..]
+            .iter()
+            .position(|&rate| rate <= threshold)
+            .map_or(length, |offset| peak + offset)
+    };
+    Recovery {
+        spike,
+        baseline,
+        recovery_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_working_sets() {
+        let mut generator = WorkingSets::new(8, 3, 50..=100, 0);
+        let requests: Vec<usize> = (0..2000).map(|_| generator.sample()).collect();
+        let shifts = generator.shifts().to_vec();
+        assert!(shifts.len() >= 19);
+
+        let starts = [0].into_iter().chain(shifts.iter().copied());
+        let ends = shifts.iter().copied().chain([requests.len()]);
+        let (phases, lengths): (Vec<HashSet<usize>>, Vec<usize>) = starts
+            .zip(ends)
+            .map(|(start, end)| (requests[start..end].iter().copied().collect(), end - start))
+            .unzip();
+        assert!(phases.iter().all(|phase| phase.len() <= 8));
+        assert!(
+            lengths[..lengths.len() - 1]
+                .iter()
+                .all(|length| (50..=100).contains(length))
+        );
+        // Consecutive full phases share at most the kept pages.
+        for pair in phases[..phases.len() - 1].windows(2) {
+            assert!(pair[0].intersection(&pair[1]).count() <= 3);
+        }
+    }
+
+    #[test]
+    fn test_recovery() {
+        // Phases of 100 requests that miss for the first 10 requests after each shift and
+        // once every 10 requests after that.
+        let costs: Vec<usize> = (0..1000)
+            .map(|i| usize::from(i % 100 < 10 || i % 10 == 9))
+            .collect();
+        let shifts: Vec<usize> = (1..10).map(|phase| phase * 100).collect();
+        let recovery = recovery(&costs, &shifts, 5);
+        assert_eq!(recovery.spike, 1.0);
+        assert_eq!(recovery.baseline, 0.1);
+        assert_eq!(recovery.recovery_time, 10);
+    }
+}
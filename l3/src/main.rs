@@ -1,30 +1,34 @@
 mod packing;
-mod sampler;
 
+use online_core::progress::progress_bar;
+use online_core::results::ResultWriter;
+use online_core::sampler::{DistributionType, RandomSampler};
+use online_core::seed::{derive_seed, master_seed};
+use online_core::{OnlineAlgorithm, run};
 use packing::{BinPackingManager, PackingStrategy};
-use rand::Rng;
-use sampler::{DistributionType, RandomSampler};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 use rayon::prelude::*;
-use std::fs::File;
-use std::io::{self, Write};
-use std::sync::{Arc, Mutex};
+use std::io;
 use std::time::Instant;
-use indicatif::ProgressBar;
 
 const SAMPLE_SIZE: usize = 100_000;
 const TOTAL_ITEMS: usize = 100;
 
+/// Produces a fresh strategy, seeding its RNG (if any) with the given seed.
+type StrategyFactory = Box<dyn Fn(u64) -> PackingStrategy + Sync>;
+
 fn main() -> io::Result<()> {
     // Start the timer to measure total execution time.
     let start_time = Instant::now();
+    let seed = master_seed();
+    println!("Master seed: {}", seed);
 
     // Create and open the CSV file for writing.
-    let file = File::create("results.csv")?;
-    let file = Mutex::new(file); // Wrap the file in a Mutex for synchronized access
-    writeln!(
-        file.lock().unwrap(),
-        "distribution;strategy;experiment;bin_count;item_sum"
+    let file = ResultWriter::create(
+        "results.csv",
+        "distribution;strategy;experiment;seed;bin_count;item_sum",
     )?;
 
     // Define the distribution types.
@@ -35,73 +39,70 @@ fn main() -> io::Result<()> {
         DistributionType::Geometric,
     ];
 
-    // Define the packing strategies as factories (closures).
-    let strategy_factories: Vec<(&str, Box<dyn Fn() -> PackingStrategy + Sync>)> = vec![
-        ("NextFit", Box::new(|| PackingStrategy::NextFit)),
-        ("RandomFit", Box::new(|| PackingStrategy::RandomFit(rand::rng()))),
-        ("FirstFit", Box::new(|| PackingStrategy::FirstFit)),
-        ("BestFit", Box::new(|| PackingStrategy::BestFit)),
-        ("WorstFit", Box::new(|| PackingStrategy::WorstFit)),
+    // Define the packing strategies as factories (closures taking a seed).
+    let strategy_factories: Vec<StrategyFactory> = vec![
+        Box::new(|_| PackingStrategy::NextFit),
+        Box::new(|seed| PackingStrategy::RandomFit(StdRng::seed_from_u64(seed))),
+        Box::new(|_| PackingStrategy::FirstFit),
+        Box::new(|_| PackingStrategy::BestFit),
+        Box::new(|_| PackingStrategy::WorstFit),
     ];
 
     // Calculate the total number of iterations for the progress bar.
     let total_iterations = distributions.len() * strategy_factories.len();
-    let progress_bar = Arc::new(Mutex::new(ProgressBar::new(total_iterations as u64)));
+    let progress_bar = progress_bar(total_iterations as u64);
 
     // Use parallel iterators for multithreading.
-    distributions.par_iter().for_each(|distribution| {
-        strategy_factories.par_iter().for_each(|(strategy_name, strategy_factory)| {
+    distributions.par_iter().enumerate().for_each(|(distribution_index, distribution)| {
+        strategy_factories.par_iter().enumerate().for_each(|(strategy_index, strategy_factory)| {
             let mut results = Vec::new();
             for experiment in 0..SAMPLE_SIZE {
+                // Each row can be regenerated from its seed alone.
+                let row_seed = derive_seed(
+                    seed,
+                    &[distribution_index as u64, strategy_index as u64, experiment as u64],
+                );
                 // Create a sampler for the current distribution.
-                let mut sampler = RandomSampler::new(*distribution, 10);
-                let mut manager = BinPackingManager::new(strategy_factory());
-                let mut rng: rand::prelude::ThreadRng = rand::rng(); // Thread-local RNG for safety.
-                let mut total_items = 0;
-                let mut item_sum = 0.0;
-
-                // Add items until the total reaches TOTAL_ITEMS.
-                while total_items < TOTAL_ITEMS {
+                let mut sampler = RandomSampler::new(*distribution, 10, derive_seed(row_seed, &[0]));
+                let mut manager = BinPackingManager::new(strategy_factory(derive_seed(row_seed, &[1])));
+                let mut rng = StdRng::seed_from_u64(derive_seed(row_seed, &[2])); // Item weights.
+                let mut items = Vec::with_capacity(TOTAL_ITEMS);
+
+                // Generate items until the total reaches TOTAL_ITEMS.
+                while items.len() < TOTAL_ITEMS {
                     let k = sampler.sample(); // Random k from 1 to 10.
                     for _ in 0..k {
-                        if total_items >= TOTAL_ITEMS {
+                        if items.len() >= TOTAL_ITEMS {
                             break;
                         }
-                        let item: f64 = rng.random_range(0.0..=1.0); // Random item weight between 0 and 1.
-                        manager.add_item(item);
-                        item_sum += item; // Accumulate the sum of items.
-                        total_items += 1;
+                        items.push(rng.random_range(0.0..=1.0)); // Random item weight between 0 and 1.
                     }
                 }
+                let item_sum: f64 = items.iter().sum();
 
-                // Calculate the number of bins used.
-                let bin_count = manager.bins().len();
+                // Every opened bin costs 1, so the cost is the number of bins used.
+                let bin_count = run(&mut manager, items);
 
                 // Format the result as a CSV row.
                 results.push(format!(
-                    "{:?};{};{};{};{:.2}",
-                    distribution, strategy_name, experiment, bin_count, item_sum
+                    "{:?};{};{};{};{};{:.2}",
+                    distribution, manager.name(), experiment, row_seed, bin_count, item_sum
                 ));
             }
 
             // Increment the progress bar.
-            let progress_bar = progress_bar.clone();
-            let pb = progress_bar.lock().unwrap();
-            pb.inc(1);
+            progress_bar.inc(1);
             // Write results to the CSV file in a thread-safe manner.
-            let mut file = file.lock().unwrap();
-            for result in results {
-                writeln!(file, "{}", result).expect("Failed to write to file");
-            }
+            file.write_rows(results).expect("Failed to write to file");
         });
     });
 
     // Finish the progress bar.
-    progress_bar.lock().unwrap().finish();
+    progress_bar.finish();
 
     // Print the total elapsed time.
     let elapsed_time = start_time.elapsed();
     println!("Total time elapsed: {:.2?}", elapsed_time);
 
-    Ok(())
+    file.finish()
 }
\ No newline at end of file
@@ -1,16 +1,19 @@
+use online_core::OnlineAlgorithm;
+use online_core::seed::splitmix64;
+use rand::rngs::StdRng;
 use rand::seq::IteratorRandom;
-use rand::rngs::ThreadRng;
 
 /// The capacity of each bin.
 const BIN_CAPACITY: f64 = 1.0;
 
 /// Enum representing different bin packing strategies.
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum PackingStrategy {
     /// Next-Fit strategy: Place the item in the last bin if it fits; otherwise, start a new bin.
     NextFit,
     /// Random-Fit strategy: Place the item in a randomly chosen bin that has enough space.
-    RandomFit(ThreadRng),
+    RandomFit(StdRng),
     /// First-Fit strategy: Place the item in the first bin that has enough space.
     FirstFit,
     /// Best-Fit strategy: Place the item in the bin that leaves the least leftover space.
@@ -19,6 +22,19 @@ pub enum PackingStrategy {
     WorstFit,
 }
 
+impl PackingStrategy {
+    /// Returns the name of the strategy, as used in the results.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PackingStrategy::NextFit => "NextFit",
+            PackingStrategy::RandomFit(_) => "RandomFit",
+            PackingStrategy::FirstFit => "FirstFit",
+            PackingStrategy::BestFit => "BestFit",
+            PackingStrategy::WorstFit => "WorstFit",
+        }
+    }
+}
+
 /// A manager for handling bin packing operations.
 #[derive(Debug)]
 pub struct BinPackingManager {
@@ -42,6 +58,7 @@ impl BinPackingManager {
     }
 
     /// Returns a reference to the current list of bins.
+    #[allow(dead_code)]
     pub fn bins(&self) -> &[f64] {
         &self.bins
     }
@@ -63,11 +80,11 @@ impl BinPackingManager {
 
     /// Implements the Next-Fit strategy.
     fn _next_fit(&mut self, item: f64) {
-        if let Some(last) = self.bins.last_mut() {
-            if *last + item <= BIN_CAPACITY {
-                *last += item;
-                return;
-            }
+        if let Some(last) = self.bins.last_mut()
+            && *last + item <= BIN_CAPACITY
+        {
+            *last += item;
+            return;
         }
         // Start a new bin if the item doesn't fit in the last bin.
         self.bins.push(item);
@@ -138,10 +155,35 @@ impl BinPackingManager {
     }
 }
 
+/// Packs items, charging 1 for every bin opened, so the total cost is the number of bins.
+impl OnlineAlgorithm<f64> for BinPackingManager {
+    fn serve(&mut self, item: f64) -> u64 {
+        let bins = self.bins.len();
+        self.add_item(item);
+        (self.bins.len() - bins) as u64
+    }
+
+    /// Empties every bin, keeping the random number generator of Random-Fit.
+    fn reset(&mut self) {
+        self.bins.clear();
+    }
+
+    fn name(&self) -> &str {
+        self.strategy.name()
+    }
+
+    /// Hashes the bin loads in order. No bins hash to 0.
+    fn snapshot(&self) -> u64 {
+        self.bins
+            .iter()
+            .fold(0, |hash, load| splitmix64(hash ^ load.to_bits()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::rng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_next_fit() {
@@ -154,7 +196,7 @@ mod tests {
 
     #[test]
     fn test_random_fit() {
-        let mut mgr = BinPackingManager::new(PackingStrategy::RandomFit(rng()));
+        let mut mgr = BinPackingManager::new(PackingStrategy::RandomFit(StdRng::seed_from_u64(0)));
         for _ in 0..4 {
             mgr.add_item(0.5);
         }
@@ -190,4 +232,21 @@ mod tests {
         }
         assert_eq!(mgr.bins(), &[1.0, 0.75]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_online_algorithm() {
+        let items = [0.5, 0.25, 0.75, 0.25, 0.5];
+        let mut mgr = BinPackingManager::new(PackingStrategy::FirstFit);
+        assert_eq!(OnlineAlgorithm::name(&mgr), "FirstFit");
+        // The cost is the number of bins opened.
+        assert_eq!(online_core::run(&mut mgr, items), 3);
+        assert_eq!(mgr.bins(), &[1.0, 0.75, 0.5]);
+        let snapshot = mgr.snapshot();
+
+        mgr.reset();
+        assert!(mgr.bins().is_empty());
+        assert_eq!(mgr.snapshot(), 0);
+        assert_eq!(online_core::run(&mut mgr, items), 3);
+        assert_eq!(mgr.snapshot(), snapshot);
+    }
+}
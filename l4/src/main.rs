@@ -1,10 +1,12 @@
 // src/main.rs
-use indicatif::{ProgressBar, ProgressStyle};
-use rand::{distr::weighted::WeightedIndex, prelude::Distribution, prelude::ThreadRng, rng, Rng};
+use online_core::progress::progress_bar;
+use online_core::results::ResultWriter;
+use online_core::sampler::{DistributionType, RandomSampler};
+use online_core::seed::{derive_seed, master_seed, splitmix64};
+use online_core::{run, OnlineAlgorithm};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::prelude::*;
 use std::error::Error;
-use std::fs::File;
-use std::io::{BufWriter, Write};
 use std::sync::Arc;
 
 // ——— Metric & Algorithms ——————————————————————————————————————
@@ -41,13 +43,25 @@ impl GraphStructure {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
 pub enum MigrationType {
     MoveToMin,
-    CoinFlip,
+    /// Flips its coins with the given RNG.
+    CoinFlip(StdRng),
+}
+
+impl MigrationType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MigrationType::MoveToMin => "MoveToMin",
+            MigrationType::CoinFlip(_) => "CoinFlip",
+        }
+    }
 }
 
 pub struct PageMigration {
+    start: usize,
     page: usize,
     d: usize,
     metric: GraphStructure,
@@ -59,6 +73,7 @@ impl PageMigration {
     pub fn new(start: usize, d: usize, metric: GraphStructure, policy: MigrationType) -> Self {
         assert!(start < 64);
         PageMigration {
+            start,
             page: start,
             d,
             metric,
@@ -68,12 +83,12 @@ impl PageMigration {
     }
 
     /// Serve one request; return access + (optional) migration cost.
-    pub fn on_request(&mut self, req: usize, rng: &mut ThreadRng) -> usize {
+    pub fn on_request(&mut self, req: usize) -> usize {
         assert!(req < 64);
         let dist = self.metric.distance(self.page, req);
         let mut cost = dist;
 
-        match self.policy {
+        match &mut self.policy {
             MigrationType::MoveToMin => {
                 self.buffer.push(req);
                 if self.buffer.len() == self.d {
@@ -91,7 +106,7 @@ impl PageMigration {
                     self.buffer.clear();
                 }
             }
-            MigrationType::CoinFlip => {
+            MigrationType::CoinFlip(rng) => {
                 let p = 1.0 / (2.0 * (self.d as f64));
                 if rng.random_bool(p) {
                     cost += self.d * dist;
@@ -104,22 +119,36 @@ impl PageMigration {
     }
 }
 
-// ——— Main Simulation ——————————————————————————————————————
+impl OnlineAlgorithm<usize> for PageMigration {
+    fn serve(&mut self, req: usize) -> u64 {
+        self.on_request(req) as u64
+    }
 
-fn uniform_weights(n: usize) -> Vec<f64> {
-    vec![1.0 / (n as f64); n]
-}
-fn harmonic_weights(n: usize) -> Vec<f64> {
-    let h: f64 = (1..=n).map(|i| 1.0 / (i as f64)).sum();
-    (1..=n).map(|i| (1.0 / (i as f64)) / h).collect()
-}
-fn biharmonic_weights(n: usize) -> Vec<f64> {
-    let h2: f64 = (1..=n).map(|i| 1.0 / ((i*i) as f64)).sum();
-    (1..=n).map(|i| (1.0 / ((i*i) as f64)) / h2).collect()
+    /// Moves the page back to its start and forgets buffered requests; the RNG of CoinFlip
+    /// keeps its state.
+    fn reset(&mut self) {
+        self.page = self.start;
+        self.buffer.clear();
+    }
+
+    fn name(&self) -> &str {
+        self.policy.name()
+    }
+
+    /// Hashes the node holding the page and the buffered requests, in order.
+    fn snapshot(&self) -> u64 {
+        self.buffer
+            .iter()
+            .fold(splitmix64(self.page as u64), |acc, &v| splitmix64(acc ^ v as u64))
+    }
 }
 
+// ——— Main Simulation ——————————————————————————————————————
+
 fn main() -> Result<(), Box<dyn Error>> {
     // parameters
+    let seed = master_seed();
+    println!("Master seed: {}", seed);
     let n = 64;
     let req_len = 65_536;
     let ds = [16, 32, 64, 128, 256];
@@ -128,13 +157,14 @@ fn main() -> Result<(), Box<dyn Error>> {
         (GraphStructure::Hypercube, "Hypercube 6D"),
     ];
     let dists = [
-        (uniform_weights(n),   "Uniform"),
-        (harmonic_weights(n),  "Harmonic"),
-        (biharmonic_weights(n),"Biharmonic"),
+        (DistributionType::Uniform,        "Uniform"),
+        (DistributionType::Harmonic,       "Harmonic"),
+        (DistributionType::DoublyHarmonic, "Biharmonic"),
     ];
-    let algos = [
-        (MigrationType::MoveToMin, "MoveToMin"),
-        (MigrationType::CoinFlip,  "CoinFlip"),
+    // policies are built per iteration, so CoinFlip gets an RNG seeded from the row's seed
+    let algos: [fn(StdRng) -> MigrationType; 2] = [
+        |_| MigrationType::MoveToMin,
+        MigrationType::CoinFlip,
     ];
     let iterations = 1_000;
 
@@ -142,27 +172,25 @@ fn main() -> Result<(), Box<dyn Error>> {
     struct Task {
         metric: GraphStructure,
         gname: &'static str,
-        weights: Vec<f64>,
+        dist: DistributionType,
         dname: &'static str,
         d: usize,
-        policy: MigrationType,
-        pname: &'static str,
+        policy: fn(StdRng) -> MigrationType,
     }
     let mut tasks = Vec::with_capacity(
         graphs.len() * dists.len() * ds.len() * algos.len()
     );
     for &(metric, gname) in &graphs {
-        for (weights, dname) in &dists {
+        for &(dist, dname) in &dists {
             for &d in &ds {
-                for &(policy, pname) in &algos {
+                for &policy in &algos {
                     tasks.push(Task {
                         metric,
                         gname,
-                        weights: weights.clone(),
+                        dist,
                         dname,
                         d,
                         policy,
-                        pname,
                     });
                 }
             }
@@ -171,35 +199,29 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // progress bar
     let total = tasks.len() * iterations;
-    let pb = Arc::new(ProgressBar::new(total as u64));
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")?
-            .progress_chars("#>-"),
-    );
+    let pb = Arc::new(progress_bar(total as u64));
 
     // run all tasks in parallel
     let results: Vec<String> = tasks
     .into_par_iter()
-    .flat_map_iter(|task| {
-        // prepare RNG / sampler
-        let mut rng = rng();
-        let sampler = WeightedIndex::new(&task.weights).unwrap();
-
+    .enumerate()
+    .flat_map_iter(|(task_index, task)| {
         // now return a _normal_ iterator of Strings
         (0..iterations).map({
             let pb = Arc::clone(&pb);
-            move |_| {
+            move |iteration| {
+                // every iteration gets its own RNGs, so each row can be regenerated from its seed
+                let row_seed = derive_seed(seed, &[task_index as u64, iteration as u64]);
+                let mut sampler = RandomSampler::new(task.dist, n, derive_seed(row_seed, &[0]));
+                let rng = StdRng::seed_from_u64(derive_seed(row_seed, &[1]));
                 // build sim, run it, format your CSV line
-                let mut sim = PageMigration::new(0, task.d, task.metric, task.policy);
                 let reqs: Vec<usize> = (0..req_len)
-                    .map(|_| sampler.sample(&mut rng))
+                    .map(|_| sampler.sample() as usize - 1) // nodes are 0-based
                     .collect();
-                let cost: usize = reqs.into_iter()
-                    .map(|r| sim.on_request(r, &mut rng))
-                    .sum();
+                let mut sim = PageMigration::new(0, task.d, task.metric, (task.policy)(rng));
+                let cost = run(&mut sim, reqs);
                 pb.inc(1);
-                format!("{},{},{},{},{}", task.gname, task.dname, task.d, task.pname, cost)
+                format!("{},{},{},{},{},{}", task.gname, task.dname, task.d, sim.name(), row_seed, cost)
             }
         })
     })
@@ -209,13 +231,31 @@ fn main() -> Result<(), Box<dyn Error>> {
     pb.finish_with_message("Simulation complete!");
 
     // write CSV
-    let f = File::create("results.csv")?;
-    let mut w = BufWriter::new(f);
-    writeln!(w, "Graph,Distribution,D,Algorithm,Cost")?;
-    for line in results {
-        writeln!(w, "{}", line)?;
-    }
-    w.flush()?;
+    let w = ResultWriter::create("results.csv", "Graph,Distribution,D,Algorithm,Seed,Cost")?;
+    w.write_rows(results)?;
+    w.finish()?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_replays() {
+        let reqs = [1, 1, 3, 3, 7, 7, 7, 0, 63, 63];
+        let mut sim = PageMigration::new(0, 2, GraphStructure::Hypercube, MigrationType::MoveToMin);
+        let fresh = sim.snapshot();
+        assert_eq!(sim.name(), "MoveToMin");
+
+        let cost = run(&mut sim, reqs);
+        let end = sim.snapshot();
+        assert_ne!(end, fresh);
+
+        sim.reset();
+        assert_eq!(sim.snapshot(), fresh);
+        assert_eq!(run(&mut sim, reqs), cost);
+        assert_eq!(sim.snapshot(), end);
+    }
+}
@@ -1,11 +1,12 @@
 use std::collections::HashSet;
 use std::error::Error;
-use std::fs::File;
 
-use csv::Writer;
-use indicatif::{ProgressBar, ProgressStyle};
+use online_core::progress::progress_bar;
+use online_core::results::ResultWriter;
+use online_core::seed::{derive_seed, master_seed, splitmix64};
+use online_core::{run, OnlineAlgorithm};
 use rand::distr::Uniform;
-use rand::{prelude::*, rng};
+use rand::prelude::*;
 use rayon::prelude::*;
 
 // Number of pages/nodes
@@ -25,15 +26,16 @@ enum CounterState {
     Waiting,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum Request {
     Read(usize),
     Write(usize),
 }
 
 impl Request {
-    /// Flip a biased coin (probability `p` of Write, else Read).
-    pub fn new_random_request(p: f64, page: usize) -> Self {
-        if rand::random_bool(p) {
+    /// Flip a biased coin (probability `p` of Write, else Read) using the given RNG.
+    pub fn new_random_request<R: Rng + ?Sized>(rng: &mut R, p: f64, page: usize) -> Self {
+        if rng.random_bool(p) {
             Request::Write(page)
         } else {
             Request::Read(page)
@@ -103,7 +105,7 @@ impl PageAllocation {
     ///  - If counter == threshold, replicate (cost += threshold).
     ///  - Finally, “decay” every other page’s counter and possibly evict.
     fn process_write(&mut self, page: usize) -> u64 {
-        let idx = page as usize;
+        let idx = page;
         let mut cost = 0;
         let current_copies = self.copies.len() as u64;
 
@@ -133,7 +135,7 @@ impl PageAllocation {
         // Decay step: for every other page, if it's a replica with counter > 0, decrement.
         // If that counter hits 0 in Waiting, evict (unless it's the last copy).
         for other in 0..NODES {
-            if other as usize != page {
+            if other != page {
                 self.process_write_by_another_page(other);
             }
         }
@@ -147,20 +149,18 @@ impl PageAllocation {
     ///    • If more than one replica remains, remove it & set state→Normal.
     ///    • Else (sole copy), set state→Waiting.
     fn process_write_by_another_page(&mut self, page: usize) {
-        if self.counts[page].0 > 0 && self.copies.contains(&(page as usize)) {
+        if self.counts[page].0 > 0 && self.copies.contains(&page) {
             self.counts[page].0 -= 1;
         }
 
-        if self.counts[page].0 == 0 {
-            if self.copies.contains(&(page as usize)) {
-                if self.copies.len() > 1 {
-                    // Evict this replica
-                    self.copies.remove(&(page as usize));
-                    self.counts[page].1 = CounterState::Normal;
-                } else {
-                    // Keep the sole remaining replica in Waiting
-                    self.counts[page].1 = CounterState::Waiting;
-                }
+        if self.counts[page].0 == 0 && self.copies.contains(&page) {
+            if self.copies.len() > 1 {
+                // Evict this replica
+                self.copies.remove(&page);
+                self.counts[page].1 = CounterState::Normal;
+            } else {
+                // Keep the sole remaining replica in Waiting
+                self.counts[page].1 = CounterState::Waiting;
             }
         }
     }
@@ -180,10 +180,10 @@ impl PageAllocation {
             if let Some(&victim) = self
                 .copies
                 .iter()
-                .find(|&&p| self.counts[p as usize].1 == CounterState::Waiting)
+                .find(|&&p| self.counts[p].1 == CounterState::Waiting)
             {
                 self.copies.remove(&victim);
-                self.counts[victim as usize].1 = CounterState::Normal;
+                self.counts[victim].1 = CounterState::Normal;
             }
 
             // Cost in tokens to replicate
@@ -194,39 +194,59 @@ impl PageAllocation {
     }
 }
 
+impl OnlineAlgorithm<Request> for PageAllocation {
+    fn serve(&mut self, request: Request) -> u64 {
+        self.process_request(&request)
+    }
+
+    /// Go back to the single replica at page 0, forgetting the peak replication degree.
+    fn reset(&mut self) {
+        *self = Self::new(self.threshold);
+    }
+
+    fn name(&self) -> &str {
+        "Counting"
+    }
+
+    /// Hash the set of replicas together with every page's counter and state.
+    fn snapshot(&self) -> u64 {
+        let copies = self
+            .copies
+            .iter()
+            .fold(0, |hash, &page| hash ^ splitmix64(page as u64));
+        self.counts.iter().fold(copies, |hash, &(count, state)| {
+            splitmix64(hash ^ (count << 1 | (state == CounterState::Waiting) as u64))
+        })
+    }
+}
+
 /// Simulate exactly `REQUESTS` operations with write‐probability `p` and threshold `d`.
 /// Returns a tuple `(sum_of_all_request_costs, peak_replication_degree)`.
 fn simulate<R: Rng + ?Sized>(rng: &mut R, p: f64, threshold: u64) -> (f64, usize) {
     let mut alloc = PageAllocation::new(threshold);
-    let mut total_cost = 0u64;
     let node_dist = Uniform::new(0, NODES).expect("Uniform distribution should be valid");
 
-    for _ in 0..REQUESTS {
-        let page = node_dist.sample(rng) as usize;
-        let req = Request::new_random_request(p, page);
-        total_cost += alloc.process_request(&req);
-    }
+    // Requests are drawn lazily, each right before it is served
+    let requests = (0..REQUESTS).map(|_| {
+        let page = node_dist.sample(rng);
+        Request::new_random_request(rng, p, page)
+    });
+    let total_cost = run(&mut alloc, requests);
 
     // Return the raw total cost (as f64) and the maximum replication degree
     (total_cost as f64, alloc.max_copies() as usize)
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let seed = master_seed();
+    println!("Master seed: {}", seed);
+
     // 1) Open CSV and write header
-    let file = File::create("results.csv")?;
-    let mut wtr = Writer::from_writer(file);
-    wtr.write_record(&["D", "p", "avg_cost", "avg_max_copies"])?;
+    let wtr = ResultWriter::create("results.csv", "D,p,seed,avg_cost,avg_max_copies")?;
 
     // 2) Set up a progress bar counting all (d, p, run) combinations
     let total_runs = (DS.len() * PS.len() * RUNS) as u64;
-    let pb = ProgressBar::new(total_runs);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template(
-                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
-            )?
-            .progress_chars("=>-"),
-    );
+    let pb = progress_bar(total_runs);
 
     // 3) Build a Vec of all (threshold, p) pairs
     let combos: Vec<(u64, f64)> = DS
@@ -235,10 +255,12 @@ fn main() -> Result<(), Box<dyn Error>> {
         .collect();
 
     // 4) For each (d, p), run `RUNS` independent trials in parallel
-    let aggregated: Vec<(u64, f64, f64, f64)> = combos
+    let aggregated: Vec<(u64, f64, u64, f64, f64)> = combos
         .par_iter()
-        .map(|&(threshold, p)| {
-            let mut local_rng = rng();
+        .enumerate()
+        .map(|(combo_index, &(threshold, p))| {
+            let combo_seed = derive_seed(seed, &[combo_index as u64]);
+            let mut local_rng = StdRng::seed_from_u64(combo_seed);
             let mut sum_total_cost = 0.0;
             let mut sum_maxcopies = 0.0;
 
@@ -252,22 +274,52 @@ fn main() -> Result<(), Box<dyn Error>> {
             // Compute per‐(d,p) averages over all RUNS
             let avg_total_cost = sum_total_cost / (RUNS as f64);
             let avg_max_copies = sum_maxcopies / (RUNS as f64);
-            (threshold, p, avg_total_cost, avg_max_copies)
+            (threshold, p, combo_seed, avg_total_cost, avg_max_copies)
         })
         .collect();
 
-    // 5) Write each (d, p, avg_total_cost, avg_max_copies) to CSV
-    for (d, p, avg_cost, avg_max) in aggregated {
-        wtr.write_record(&[
-            d.to_string(),
-            format!("{:.2}", p),
-            format!("{:.2}", avg_cost),
-            format!("{:.2}", avg_max),
-        ])?;
-    }
+    // 5) Write each (d, p, seed, avg_total_cost, avg_max_copies) to CSV
+    wtr.write_rows(aggregated.into_iter().map(|(d, p, combo_seed, avg_cost, avg_max)| {
+        format!("{},{:.2},{},{:.2},{:.2}", d, p, combo_seed, avg_cost, avg_max)
+    }))?;
 
-    wtr.flush()?;
+    wtr.finish()?;
     pb.finish_with_message("Simulation complete");
     println!("Results written to results.csv");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting() {
+        let mut alloc = PageAllocation::new(2);
+        let fresh = alloc.snapshot();
+        assert_eq!(alloc.name(), "Counting");
+
+        // A read hit is free, a read miss costs 1, and the second miss replicates the page
+        // for the threshold, evicting the waiting copy. A write costs one message per other
+        // copy.
+        let requests = [
+            (Request::Read(0), 0),
+            (Request::Read(1), 1),
+            (Request::Read(1), 3),
+            (Request::Write(1), 0),
+            (Request::Write(2), 1),
+            (Request::Write(2), 1),
+        ];
+        for (request, expected) in requests {
+            assert_eq!(alloc.serve(request), expected);
+        }
+        assert_eq!(alloc.max_copies(), 2);
+        let end = alloc.snapshot();
+
+        alloc.reset();
+        assert_eq!(alloc.snapshot(), fresh);
+        assert_eq!(alloc.max_copies(), 1);
+        assert_eq!(run(&mut alloc, requests.map(|(request, _)| request)), 6);
+        assert_eq!(alloc.snapshot(), end);
+    }
+}
@@ -0,0 +1,172 @@
+//! The core shared by the lab crates: the interface of their online algorithms, an experiment
+//! runner that serves requests and compares costs to an offline optimum, and the request
+//! distributions, seeding, result files and progress bars of their experiments.
+
+pub mod progress;
+pub mod results;
+pub mod sampler;
+pub mod seed;
+
+/// An online algorithm serving requests one at a time, without knowledge of the requests to
+/// come.
+pub trait OnlineAlgorithm<Request> {
+    /// Serves a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to serve.
+    ///
+    /// # Returns
+    ///
+    /// The cost of serving the request.
+    fn serve(&mut self, request: Request) -> u64;
+
+    /// Restores the state the algorithm started in, as if it had served no request.
+    /// Randomized algorithms keep drawing from their random number generator, so a run after
+    /// a reset sees fresh random choices.
+    fn reset(&mut self);
+
+    /// Returns the name of the algorithm, as used in the results.
+    fn name(&self) -> &str;
+
+    /// Returns a hash of the current state of the algorithm, so that two runs can be checked
+    /// to pass through the same states.
+    fn snapshot(&self) -> u64;
+}
+
+/// Serves requests in order.
+///
+/// # Arguments
+///
+/// * `algorithm` - The algorithm serving the requests, in whatever state it is in.
+/// * `requests` - The requests to serve.
+///
+/// # Returns
+///
+/// The total cost of the requests.
+pub fn run<Request, A>(algorithm: &mut A, requests: impl IntoIterator<Item = Request>) -> u64
+where
+    A: OnlineAlgorithm<Request> + ?Sized,
+{
+    requests
+        .into_iter()
+        .map(|request| algorithm.serve(request))
+        .sum()
+}
+
+/// The cost of one algorithm on a request sequence next to the optimal offline cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The name of the algorithm.
+    pub name: String,
+    /// The total cost of the algorithm.
+    pub cost: u64,
+    /// The optimal offline cost of the same requests.
+    pub opt_cost: u64,
+}
+
+impl Report {
+    /// Returns the ratio of the cost to the optimal cost: 1 if both are 0, and infinity if
+    /// only the optimal cost is.
+    pub fn competitive_ratio(&self) -> f64 {
+        match (self.cost, self.opt_cost) {
+            (0, 0) => 1.0,
+            (_, 0) => f64::INFINITY,
+            (cost, opt_cost) => cost as f64 / opt_cost as f64,
+        }
+    }
+}
+
+/// Resets every algorithm, serves the same requests with each and compares their costs to
+/// the optimal offline cost.
+///
+/// # Arguments
+///
+/// * `algorithms` - The algorithms to compare.
+/// * `requests` - The requests every algorithm serves.
+/// * `opt_cost` - The optimal offline cost of the requests.
+///
+/// # Returns
+///
+/// One report per algorithm, in order.
+pub fn compare<'a, Request, A>(
+    algorithms: impl IntoIterator<Item = &'a mut A>,
+    requests: &[Request],
+    opt_cost: u64,
+) -> Vec<Report>
+where
+    Request: Clone,
+    A: OnlineAlgorithm<Request> + ?Sized + 'a,
+{
+    algorithms
+        .into_iter()
+        .map(|algorithm| {
+            algorithm.reset();
+            let cost = run(algorithm, requests.iter().cloned());
+            Report {
+                name: algorithm.name().to_string(),
+                cost,
+                opt_cost,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A counter that charges the distance from the previous request.
+    struct Walker {
+        position: u64,
+    }
+
+    impl OnlineAlgorithm<u64> for Walker {
+        fn serve(&mut self, request: u64) -> u64 {
+            let cost = self.position.abs_diff(request);
+            self.position = request;
+            cost
+        }
+
+        fn reset(&mut self) {
+            self.position = 0;
+        }
+
+        fn name(&self) -> &str {
+            "Walker"
+        }
+
+        fn snapshot(&self) -> u64 {
+            self.position
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let mut walker = Walker { position: 0 };
+        assert_eq!(run(&mut walker, [3, 1, 4]), 8);
+        assert_eq!(walker.snapshot(), 4);
+        walker.reset();
+        assert_eq!(walker.snapshot(), 0);
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut walkers: Vec<Box<dyn OnlineAlgorithm<u64>>> = vec![
+            Box::new(Walker { position: 4 }),
+            Box::new(Walker { position: 0 }),
+        ];
+        // Both walkers are reset before serving, so they report the same cost.
+        let reports = compare(walkers.iter_mut().map(|walker| walker.as_mut()), &[2, 2], 1);
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|report| report.cost == 2));
+        assert_eq!(reports[0].competitive_ratio(), 2.0);
+
+        let free = Report {
+            name: "Free".to_string(),
+            cost: 0,
+            opt_cost: 0,
+        };
+        assert_eq!(free.competitive_ratio(), 1.0);
+    }
+}
@@ -0,0 +1,25 @@
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// The layout of the progress bars of every experiment.
+const TEMPLATE: &str =
+    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})";
+
+/// Creates a progress bar for an experiment, shared by its workers.
+///
+/// # Arguments
+///
+/// * `len` - The number of steps of the experiment.
+///
+/// # Returns
+///
+/// A progress bar at step 0. It can be advanced from any thread.
+pub fn progress_bar(len: u64) -> ProgressBar {
+    let progress_bar = ProgressBar::new(len);
+    progress_bar.set_style(
+        ProgressStyle::default_bar()
+            .template(TEMPLATE)
+            .expect("The progress bar template is valid")
+            .progress_chars("#>-"),
+    );
+    progress_bar
+}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// A CSV file of results that parallel workers append rows to.
+///
+/// Rows are formatted by the caller, so any separator works. Each call writes its rows while
+/// holding a lock, so the rows of one worker are never interleaved with those of another.
+#[derive(Debug)]
+pub struct ResultWriter {
+    /// The buffered file, locked by every write.
+    file: Mutex<BufWriter<File>>,
+}
+
+impl ResultWriter {
+    /// Creates the file, truncating it if it exists, and writes its header.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    /// * `header` - The header row, without a trailing newline.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `ResultWriter`.
+    pub fn create(path: impl AsRef<Path>, header: &str) -> io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", header)?;
+        Ok(ResultWriter {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Appends rows, each followed by a newline.
+    pub fn write_rows<T: Display>(&self, rows: impl IntoIterator<Item = T>) -> io::Result<()> {
+        let mut file = self.file.lock().unwrap();
+        for row in rows {
+            writeln!(file, "{}", row)?;
+        }
+        Ok(())
+    }
+
+    /// Appends rows already joined into one string, each ending with a newline.
+    pub fn write(&self, payload: &str) -> io::Result<()> {
+        self.file.lock().unwrap().write_all(payload.as_bytes())
+    }
+
+    /// Flushes the rows still buffered and closes the file.
+    pub fn finish(self) -> io::Result<()> {
+        self.file.into_inner().unwrap().flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_writer() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("results-{}.csv", std::process::id()));
+        let writer = ResultWriter::create(&path, "a;b")?;
+        writer.write_rows([format!("{};{}", 1, 2)])?;
+        writer.write("3;4\n5;6\n")?;
+        writer.write_rows(std::iter::empty::<String>())?;
+        writer.finish()?;
+        let contents = std::fs::read_to_string(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(contents, "a;b\n1;2\n3;4\n5;6\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+/// Enum representing the distributions requests and item counts are drawn from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistributionType {
+    /// Every value is equally likely.
+    Uniform,
+    /// Value `i` has weight `1/i`.
+    Harmonic,
+    /// Value `i` has weight `1/i²`.
+    DoublyHarmonic,
+    /// Value `i` has weight `1/2^(i-1)`.
+    Geometric,
+}
+
+impl DistributionType {
+    /// Returns the unnormalized weights of the values 1 to `endpoint`, the weight of value
+    /// `i` at index `i - 1`.
+    pub fn weights(&self, endpoint: usize) -> Vec<f64> {
+        match self {
+            DistributionType::Uniform => vec![1.0; endpoint],
+            DistributionType::Harmonic => (1..=endpoint).map(|i| 1.0 / i as f64).collect(),
+            DistributionType::DoublyHarmonic => {
+                (1..=endpoint).map(|i| 1.0 / ((i * i) as f64)).collect()
+            }
+            DistributionType::Geometric => {
+                let mut weights = Vec::with_capacity(endpoint);
+                let mut current_weight = 1.0; // Start with weight 1 for 1
+                for _ in 0..endpoint {
+                    weights.push(current_weight);
+                    current_weight /= 2.0; // Next weight is half the previous
+                }
+                weights
+            }
+        }
+    }
+}
+
+/// An alias table (Walker, with Vose's construction) drawing an index in O(1) time: a
+/// uniformly chosen column yields its own index with the column's probability and its alias
+/// otherwise.
+#[derive(Debug)]
+struct AliasTable {
+    /// The probability that each column yields its own index.
+    probability: Vec<f64>,
+    /// The index each column yields otherwise.
+    alias: Vec<u32>,
+}
+
+impl AliasTable {
+    /// Builds the table of the distribution proportional to the weights in O(n) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no weights, or if one is negative or they sum to 0.
+    fn new(weights: &[f64]) -> Self {
+        let total: f64 = weights.iter().sum();
+        assert!(
+            !weights.is_empty() && weights.iter().all(|&w| w >= 0.0) && total > 0.0,
+            "A sampler needs at least one value and non-negative weights with a positive sum"
+        );
+        let n = weights.len();
+        // Scale the weights so that the average column holds exactly 1.
+        let mut probability: Vec<f64> = weights.iter().map(|&w| w * n as f64 / total).collect();
+        let mut alias: Vec<u32> = (0..n as u32).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| probability[i] < 1.0);
+        // Every column short of 1 is topped up by a column above 1, which gives away as much.
+        while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+            small.pop();
+            alias[less] = more as u32;
+            probability[more] -= 1.0 - probability[less];
+            if probability[more] < 1.0 {
+                large.pop();
+                small.push(more);
+            }
+        }
+        // What is left is 1 up to rounding errors.
+        for i in small.into_iter().chain(large) {
+            probability[i] = 1.0;
+        }
+        AliasTable { probability, alias }
+    }
+
+    /// Draws an index in O(1) time.
+    fn sample(&self, rng: &mut StdRng) -> u32 {
+        let column = rng.random_range(0..self.probability.len());
+        if rng.random::<f64>() < self.probability[column] {
+            column as u32
+        } else {
+            self.alias[column]
+        }
+    }
+}
+
+/// Draws values from {1..=endpoint} with the probabilities of a `DistributionType`.
+///
+/// The alias table of the distribution is built once, in O(endpoint) time, so every draw
+/// takes O(1) time.
+#[derive(Debug)]
+pub struct RandomSampler {
+    table: AliasTable,
+    rng: StdRng,
+}
+
+impl RandomSampler {
+    /// Creates a new RandomSampler for the given distribution type,
+    /// sampling from the range {1..=endpoint} with an RNG seeded by `seed`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `endpoint` is 0.
+    pub fn new(dist_type: DistributionType, endpoint: usize, seed: u64) -> Self {
+        Self {
+            table: AliasTable::new(&dist_type.weights(endpoint)),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Samples a value from {1, 2, …, endpoint} using the specified weighted distribution.
+    pub fn sample(&mut self) -> u32 {
+        self.table.sample(&mut self.rng) + 1 // +1 to shift from 0-based to 1-based index
+    }
+
+    /// Fills the buffer with values sampled from {1, 2, …, endpoint}, the same values
+    /// `sample` would return one by one.
+    pub fn sample_into(&mut self, values: &mut [u32]) {
+        for value in values {
+            *value = self.table.sample(&mut self.rng) + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISTRIBUTIONS: [DistributionType; 4] = [
+        DistributionType::Uniform,
+        DistributionType::Harmonic,
+        DistributionType::DoublyHarmonic,
+        DistributionType::Geometric,
+    ];
+
+    #[test]
+    fn test_weights() {
+        for distribution in DISTRIBUTIONS {
+            let weights = distribution.weights(10);
+            assert_eq!(weights.len(), 10);
+            assert_eq!(weights[0], 1.0);
+            assert!(weights.windows(2).all(|pair| pair[0] >= pair[1]));
+        }
+        assert_eq!(DistributionType::Geometric.weights(3), [1.0, 0.5, 0.25]);
+    }
+
+    /// Tests that the columns of every alias table add up to the exact distribution.
+    #[test]
+    fn test_alias_table() {
+        for distribution in DISTRIBUTIONS {
+            for endpoint in [1, 2, 10, 100] {
+                let weights = distribution.weights(endpoint);
+                let total: f64 = weights.iter().sum();
+                let table = AliasTable::new(&weights);
+                let mut mass = vec![0.0; endpoint];
+                for column in 0..endpoint {
+                    mass[column] += table.probability[column];
+                    mass[table.alias[column] as usize] += 1.0 - table.probability[column];
+                }
+                for (value, weight) in weights.iter().enumerate() {
+                    let expected = weight * endpoint as f64 / total;
+                    assert!(
+                        (mass[value] - expected).abs() < 1e-9,
+                        "{:?}, n={}: {} != {}",
+                        distribution,
+                        endpoint,
+                        mass[value],
+                        expected
+                    );
+                }
+            }
+        }
+        // Zero weights are never drawn.
+        let table = AliasTable::new(&[0.0, 1.0, 0.0, 3.0]);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!((0..1000).all(|_| [1, 3].contains(&table.sample(&mut rng))));
+    }
+
+    /// Tests the samples against the distributions with Pearson's chi-square test.
+    #[test]
+    fn test_chi_square() {
+        // The 0.999 quantile of the chi-square distribution with 9 degrees of freedom.
+        const CRITICAL_VALUE: f64 = 27.877;
+        const SAMPLES: usize = 200_000;
+        for (seed, distribution) in DISTRIBUTIONS.into_iter().enumerate() {
+            let weights = distribution.weights(10);
+            let total: f64 = weights.iter().sum();
+            let mut sampler = RandomSampler::new(distribution, 10, seed as u64);
+            let mut values = vec![0; SAMPLES];
+            sampler.sample_into(&mut values);
+            let mut counts = [0usize; 10];
+            for value in values {
+                counts[value as usize - 1] += 1;
+            }
+            let chi_square: f64 = counts
+                .iter()
+                .zip(&weights)
+                .map(|(&count, weight)| {
+                    let expected = SAMPLES as f64 * weight / total;
+                    (count as f64 - expected).powi(2) / expected
+                })
+                .sum();
+            assert!(
+                chi_square < CRITICAL_VALUE,
+                "{:?}: chi-square {} with counts {:?}",
+                distribution,
+                chi_square,
+                counts
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample_into() {
+        let mut sampler = RandomSampler::new(DistributionType::Harmonic, 100, 7);
+        let mut again = RandomSampler::new(DistributionType::Harmonic, 100, 7);
+        let mut values = [0; 1000];
+        sampler.sample_into(&mut values);
+        assert!(values.iter().all(|value| (1..=100).contains(value)));
+        assert!(values.iter().all(|&value| again.sample() == value));
+    }
+}
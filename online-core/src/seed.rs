@@ -0,0 +1,47 @@
+/// The master seed used when the `SEED` environment variable is not set.
+pub const DEFAULT_SEED: u64 = 2025;
+
+/// Reads the master seed of an experiment from the `SEED` environment variable.
+///
+/// # Panics
+///
+/// Panics if `SEED` is set to something other than an unsigned 64-bit integer.
+pub fn master_seed() -> u64 {
+    match std::env::var("SEED") {
+        Ok(seed) => seed
+            .parse()
+            .expect("SEED must be an unsigned 64-bit integer"),
+        Err(_) => DEFAULT_SEED,
+    }
+}
+
+/// Derives the seed of a single task from the master seed and the task's indices,
+/// so every task gets an independent, reproducible stream regardless of scheduling.
+pub fn derive_seed(master: u64, indices: &[u64]) -> u64 {
+    indices.iter().fold(splitmix64(master), |seed, &index| {
+        splitmix64(seed ^ splitmix64(index))
+    })
+}
+
+/// One step of the SplitMix64 generator, used as a bit mixer.
+pub fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_seed() {
+        assert_eq!(splitmix64(0), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(derive_seed(7, &[1, 2]), derive_seed(7, &[1, 2]));
+        // The order of the indices and the master seed both matter.
+        assert_ne!(derive_seed(7, &[1, 2]), derive_seed(7, &[2, 1]));
+        assert_ne!(derive_seed(7, &[1, 2]), derive_seed(8, &[1, 2]));
+        assert_eq!(derive_seed(7, &[]), splitmix64(7));
+    }
+}